@@ -15,7 +15,49 @@ pub const BONDING_CURVE_VAULT_SEED: &str = "bonding_curve_vault";
 #[constant]
 pub const USER_PURCHASE_SEED: &str = "user_purchase";
 
+#[constant]
+pub const RECEIPT_MINT_SEED: &str = "receipt_mint";
+
+#[constant]
+pub const TRANSFER_FEE_AUTHORITY_SEED: &str = "transfer_fee_authority";
+
+#[constant]
+pub const LP_LOCKER_SEED: &str = "lp_locker";
+
+#[constant]
+pub const TEMP_WSOL_SEED: &str = "temp_wsol";
+
+#[constant]
+pub const POOL_REGISTRY_SEED: &str = "pool_registry";
+
 pub const INCINERATOR: Pubkey = anchor_lang::solana_program::pubkey!("1nc1nerator11111111111111111111111111111111");
 
 #[constant]
 pub const BASE_POINTS: u64 = 10000;
+
+/// Decimals the token amounts in `Global` are expressed in.
+#[constant]
+pub const DEFAULT_TOKEN_DECIMALS: u8 = 6;
+
+#[constant]
+pub const MAX_TOKEN_DECIMALS: u8 = 9;
+
+/// Allowed deviation of the pool price from the launching price while `Global` leaves it unset.
+#[constant]
+pub const DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS: u16 = 100;
+
+/// Highest allowed deviation of the pool price from the launching price.
+#[constant]
+pub const MAX_MIGRATION_PRICE_TOLERANCE_BPS: u16 = 1000;
+
+/// Highest platform fee charged on proxy swaps.
+#[constant]
+pub const MAX_PROXY_FEE_BPS: u16 = 500;
+
+/// Highest anti-sniping surcharge at pool open.
+#[constant]
+pub const MAX_PROXY_SURCHARGE_BPS: u16 = 5000;
+
+/// Longest a migrated pool can stay closed to trading.
+#[constant]
+pub const MAX_POOL_OPEN_DELAY: u64 = 86400;
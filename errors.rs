@@ -101,4 +101,73 @@ pub enum Errors {
 
     #[msg("Token URI is too long.")]
     UriTooLong,
+
+    #[msg("A required account is missing.")]
+    MissingAccount,
+
+    #[msg("The bonding curve is paused.")]
+    BondingCurvePaused,
+
+    #[msg("The sale has already started.")]
+    SaleAlreadyStarted,
+
+    #[msg("The bonding curve already has purchases.")]
+    BondingCurveHasPurchases,
+
+    #[msg("The sale duration is out of the allowed range.")]
+    InvalidSaleDuration,
+
+    #[msg("The transfer fee exceeds the allowed limit.")]
+    TransferFeeTooHigh,
+
+    #[msg("Token decimals are out of the allowed range.")]
+    InvalidDecimals,
+
+    #[msg("The token price is not representable with these decimals.")]
+    InvalidPrice,
+
+    #[msg("Invalid quote mint.")]
+    InvalidQuoteMint,
+
+    #[msg("The launch migrates to a different venue.")]
+    InvalidMigrationTarget,
+
+    #[msg("The pool mints do not match the launch.")]
+    InvalidPoolMints,
+
+    #[msg("The LP tokens are still locked.")]
+    LpStillLocked,
+
+    #[msg("No pool fees have accrued.")]
+    NoPoolFees,
+
+    #[msg("The existing pool cannot take the deposit.")]
+    InvalidPoolDeposit,
+
+    #[msg("The curve vault holds less than its recorded reserves.")]
+    VaultBalanceMismatch,
+
+    #[msg("The curve token account holds less than its recorded reserves.")]
+    TokenBalanceMismatch,
+
+    #[msg("The pool price deviates from the launching price.")]
+    MigrationPriceMismatch,
+
+    #[msg("The pool does not belong to a migrated launch.")]
+    InvalidProxyPool,
+
+    #[msg("The launch has completed its curve but has no pool yet.")]
+    TradingUnavailable,
+
+    #[msg("The account does not hold the expected program state.")]
+    InvalidAccount,
+
+    #[msg("The LP lock is shorter than the global minimum.")]
+    LpLockTooShort,
+
+    #[msg("The pool holds less than the curve reserves.")]
+    PoolReservesTooLow,
+
+    #[msg("Only a native SOL side can omit its token account.")]
+    NativeSideNotSol,
 }
@@ -1,5 +1,7 @@
 use anchor_lang::prelude::*;
 
+use crate::state::MigrationTarget;
+
 /// Event of token creation
 #[event]
 pub struct CreateEvent {
@@ -9,6 +11,9 @@ pub struct CreateEvent {
     pub mint: Pubkey,
     pub bonding_curve: Pubkey,
     pub user: Pubkey,
+    pub launch_id: u64,
+    pub quote_mint: Pubkey,
+    pub migration_target: MigrationTarget,
     pub timestamp: i64,
 }
 
@@ -63,6 +68,12 @@ pub struct MigrateEvent {
     pub bonding_curve: Pubkey,
     pub token_amount: u64,
     pub sol_amount: u64,
+    /// LP tokens burned at migration, 0 unless the launch burns its LP.
+    pub lp_burned: u64,
+    pub pool_open_time: u64,
+    pub pool: Pubkey,
+    pub lp_mint: Pubkey,
+    pub amm_config: Pubkey,
     pub timestamp: i64,
 }
 
@@ -88,6 +99,8 @@ pub struct ProxyTradeEvent {
     pub input_token_amount: u64,
     pub output_token_amount: u64,
     pub fee_amount: u64,
+    /// Anti-sniping surcharge taken on top of `fee_amount`.
+    pub surcharge_amount: u64,
     pub fee_recipient_token_account: Pubkey,
     pub timestamp: i64,
 }
@@ -101,3 +114,121 @@ pub struct ClaimEvent {
     pub token_amount: u64,
     pub timestamp: i64,
 }
+
+/// Event of launch schedule update
+#[event]
+pub struct UpdateScheduleEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub token_investing_deadline: u64,
+    pub investing_start_at: u64,
+    pub whitelist_start_at: u64,
+    pub timestamp: i64,
+}
+
+/// Event of withdraw recipient change
+#[event]
+pub struct SetWithdrawRecipientEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub withdraw_recipient: Pubkey,
+    pub timestamp: i64,
+}
+
+/// Event of sale pause toggle
+#[event]
+pub struct SetPausedEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub paused: bool,
+    pub timestamp: i64,
+}
+
+/// Event of launch cancellation
+#[event]
+pub struct CancelEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub creator: Pubkey,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event of transfer fee harvest
+#[event]
+pub struct HarvestTransferFeesEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub creator_amount: u64,
+    pub platform_amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event of LP tokens deposited into a launch locker
+#[event]
+pub struct LockLpEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub lp_locker: Pubkey,
+    pub lp_mint: Pubkey,
+    pub amount: u64,
+    pub unlock_at: u64,
+    pub timestamp: i64,
+}
+
+/// Event of LP tokens released from a launch locker
+#[event]
+pub struct UnlockLpEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub lp_locker: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub timestamp: i64,
+}
+
+/// Event of pool fee split settings
+#[event]
+pub struct SetPoolFeeSplitEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub creator_bps: u16,
+    pub buyback_bps: u16,
+    pub timestamp: i64,
+}
+
+/// Event of trading fees collected from locked LP
+#[event]
+pub struct CollectPoolFeesEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub lp_amount: u64,
+    pub token_amount: u64,
+    pub sol_amount: u64,
+    pub creator_token_amount: u64,
+    pub creator_sol_amount: u64,
+    pub platform_token_amount: u64,
+    pub platform_sol_amount: u64,
+    pub buyback_burned: u64,
+    pub timestamp: i64,
+}
+
+/// Event of pool open delay settings
+#[event]
+pub struct SetPoolOpenDelayEvent {
+    pub user: Pubkey,
+    pub mint: Pubkey,
+    pub bonding_curve: Pubkey,
+    pub pool_open_delay: u64,
+    pub timestamp: i64,
+}
@@ -3,7 +3,8 @@ use {
     anchor_lang::{prelude::*, system_program},
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{Mint, Token},
+        token::{self, Mint as QuoteMint, Token, TokenAccount as QuoteTokenAccount, TransferChecked},
+        token_interface::{self, Mint, MintTo, TokenAccount, TokenInterface},
     },
     std::mem::size_of,
 };
@@ -18,7 +19,7 @@ pub struct Buy<'info> {
     pub global: Box<Account<'info, Global>>,
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -54,10 +55,53 @@ pub struct Buy<'info> {
         ],
         bump,
     )]
-    pub user_purchase: Box<Account<'info, UserPurchase>>,
+    pub user_purchase: Option<Box<Account<'info, UserPurchase>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            RECEIPT_MINT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = token_program,
+    )]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// SPL quote accounts, only passed for launches not raising SOL
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<Box<Account<'info, QuoteMint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub quote_vault: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = payer,
+        token::token_program = quote_token_program,
+    )]
+    pub payer_quote_account: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    pub quote_token_program: Option<Program<'info, Token>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -77,6 +121,7 @@ pub fn buy(
     let now = clock.unix_timestamp.try_into().unwrap();
 
     require!(bonding_curve.completed == false, Errors::BondingCurveComplete);
+    require!(bonding_curve.paused == false, Errors::BondingCurvePaused);
     // Buying is not allowed when the bonding_curve is not completed and the deadline is reached
     require!(bonding_curve.token_investing_deadline > now, Errors::BondingCurveEnded);
 
@@ -134,29 +179,74 @@ pub fn buy(
     bonding_curve.sol_reserves += sol_amount;
     bonding_curve.token_reserves -= token_amount;
 
-    // Transfer SOL from payer to bonding_curve
-    system_program::transfer(
-        CpiContext::new(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-                from: ctx.accounts.payer.to_account_info().clone(),
-                to: ctx.accounts.bonding_curve_vault.to_account_info().clone(),
-            },
-        ),
-        sol_amount
-    )?;
-
-    // Record user purchase instead of immediate token transfer
-    let user_purchase = &mut ctx.accounts.user_purchase;
-    if user_purchase.user == Pubkey::default() {
-        user_purchase.user = ctx.accounts.payer.key();
-        user_purchase.mint = ctx.accounts.mint.key();
-        user_purchase.token_amount = 0;
+    match quote_accounts(bonding_curve, &ctx.accounts.quote_mint, &ctx.accounts.quote_vault, &ctx.accounts.quote_token_program)? {
+        // Transfer SOL from payer to bonding_curve
+        None => system_program::transfer(
+            CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info().clone(),
+                    to: ctx.accounts.bonding_curve_vault.to_account_info().clone(),
+                },
+            ),
+            sol_amount
+        )?,
+        // Transfer quote tokens from payer to the vault
+        Some(quote) => {
+            let payer_quote_account = ctx.accounts.payer_quote_account.as_ref().ok_or(Errors::MissingAccount)?;
+            token::transfer_checked(
+                CpiContext::new(
+                    quote.token_program,
+                    TransferChecked {
+                        from: payer_quote_account.to_account_info(),
+                        mint: quote.mint,
+                        to: quote.vault,
+                        authority: ctx.accounts.payer.to_account_info(),
+                    },
+                ),
+                sol_amount,
+                quote.decimals,
+            )?
+        }
+    }
+
+    if bonding_curve.receipt_enabled {
+        // Mint transferable receipts redeemable 1:1 for tokens on claim
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(Errors::MissingAccount)?;
+        let user_receipt_account = ctx.accounts.user_receipt_account.as_ref().ok_or(Errors::MissingAccount)?;
+
+        let seeds = &[
+            BONDING_CURVE_SEED.as_bytes(),
+            &ctx.accounts.mint.key().to_bytes(),
+            &[ctx.bumps.bonding_curve],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        token_interface::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: receipt_mint.to_account_info(),
+                    to: user_receipt_account.to_account_info(),
+                    authority: bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_amount,
+        )?;
+    } else {
+        // Record user purchase instead of immediate token transfer
+        let user_purchase = ctx.accounts.user_purchase.as_mut().ok_or(Errors::MissingAccount)?;
+        if user_purchase.user == Pubkey::default() {
+            user_purchase.user = ctx.accounts.payer.key();
+            user_purchase.mint = ctx.accounts.mint.key();
+            user_purchase.token_amount = 0;
+        }
+        user_purchase.token_amount = user_purchase
+            .token_amount
+            .checked_add(token_amount)
+            .ok_or(Errors::MathOverflow)?;
     }
-    user_purchase.token_amount = user_purchase
-        .token_amount
-        .checked_add(token_amount)
-        .ok_or(Errors::MathOverflow)?;
 
     emit!(TradeEvent {
         mint: ctx.accounts.mint.key(),
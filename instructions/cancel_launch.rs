@@ -0,0 +1,214 @@
+use {
+    crate::{constants::*, errors::Errors, events::CancelEvent, state::*},
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::{
+        token::{self, Mint as QuoteMint, Token, TokenAccount as QuoteTokenAccount},
+        token_interface::{self, Burn, CloseAccount, Mint, TokenAccount, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+pub struct CancelLaunch<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_VAULT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Vault of an SPL quote token, only passed for launches not raising SOL
+    #[account(
+        mut,
+        associated_token::mint = bonding_curve.quote_mint(),
+        associated_token::authority = bonding_curve_vault,
+    )]
+    pub quote_vault: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    /// Quote mint of the launch, only passed for launches not raising SOL
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<Box<Account<'info, QuoteMint>>>,
+
+    /// Creator account of the quote token, receiving whatever was sent to the quote vault
+    #[account(
+        mut,
+        token::mint = bonding_curve.quote_mint(),
+        token::authority = creator,
+    )]
+    pub creator_quote_account: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    /// CHECK: Address validated using constraint
+    #[account(
+        mut,
+        address = bonding_curve.creator @ Errors::NotAuthorized,
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = bonding_curve.is_creator_or_authority(&payer.key(), &global) @ Errors::NotAuthorized,
+    )]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub quote_token_program: Option<Program<'info, Token>>,
+}
+
+pub fn cancel_launch(
+    ctx: Context<CancelLaunch>,
+) -> Result<()> {
+    // Getting clock
+    let clock: Clock = Clock::get()?;
+    let now = clock.unix_timestamp.try_into().unwrap();
+
+    let bonding_curve = &ctx.accounts.bonding_curve;
+
+    require!(now < bonding_curve.sale_start_at(), Errors::SaleAlreadyStarted);
+    require!(bonding_curve.has_purchases() == false, Errors::BondingCurveHasPurchases);
+
+    let seeds = &[
+        BONDING_CURVE_SEED.as_bytes(),
+        &ctx.accounts.mint.key().to_bytes(),
+        &[ctx.bumps.bonding_curve],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Burn the whole supply held by the curve
+    let token_amount = ctx.accounts.associated_bonding_curve.amount;
+    if token_amount > 0 {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: ctx.accounts.associated_bonding_curve.to_account_info(),
+                    authority: bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_amount,
+        )?;
+    }
+
+    token_interface::close_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.associated_bonding_curve.to_account_info(),
+                destination: ctx.accounts.creator.to_account_info(),
+                authority: bonding_curve.to_account_info(),
+            },
+            signer_seeds,
+        ),
+    )?;
+
+    let vault_seeds = &[
+        BONDING_CURVE_VAULT_SEED.as_bytes(),
+        &ctx.accounts.mint.key().to_bytes(),
+        &[ctx.bumps.bonding_curve_vault],
+    ];
+    let vault_signer_seeds = &[&vault_seeds[..]];
+
+    // Close the quote vault of launches not raising SOL
+    if !bonding_curve.is_sol_quote() {
+        let quote_vault = ctx.accounts.quote_vault.as_ref().ok_or(Errors::MissingAccount)?;
+        let quote_token_program = ctx.accounts.quote_token_program.as_ref().ok_or(Errors::MissingAccount)?;
+
+        // Without purchases anything in the vault was sent to it, and a close fails on a non-zero balance
+        if quote_vault.amount > 0 {
+            let quote_mint = ctx.accounts.quote_mint.as_ref().ok_or(Errors::MissingAccount)?;
+            let creator_quote_account = ctx.accounts.creator_quote_account.as_ref().ok_or(Errors::MissingAccount)?;
+            token::transfer_checked(
+                CpiContext::new_with_signer(
+                    quote_token_program.to_account_info(),
+                    token::TransferChecked {
+                        from: quote_vault.to_account_info(),
+                        mint: quote_mint.to_account_info(),
+                        to: creator_quote_account.to_account_info(),
+                        authority: ctx.accounts.bonding_curve_vault.to_account_info(),
+                    },
+                    vault_signer_seeds,
+                ),
+                quote_vault.amount,
+                quote_mint.decimals,
+            )?;
+        }
+
+        token::close_account(
+            CpiContext::new_with_signer(
+                quote_token_program.to_account_info(),
+                token::CloseAccount {
+                    account: quote_vault.to_account_info(),
+                    destination: ctx.accounts.creator.to_account_info(),
+                    authority: ctx.accounts.bonding_curve_vault.to_account_info(),
+                },
+                vault_signer_seeds,
+            ),
+        )?;
+    }
+
+    // Drain the vault, which closes it
+    let sol_amount = ctx.accounts.bonding_curve_vault.lamports();
+    if sol_amount > 0 {
+        system_program::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.bonding_curve_vault.to_account_info(),
+                    to: ctx.accounts.creator.to_account_info(),
+                },
+                vault_signer_seeds,
+            ),
+            sol_amount,
+        )?;
+    }
+
+    msg!("Launch cancelled.");
+
+    emit!(CancelEvent {
+        user: ctx.accounts.payer.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: bonding_curve.key(),
+        creator: ctx.accounts.creator.key(),
+        token_amount,
+        sol_amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    // The bonding curve itself is closed to the creator by the `close` constraint
+    Ok(())
+}
@@ -3,14 +3,14 @@ use {
     anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{self, Mint, Token, TokenAccount},
+        token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
     },
 };
 
 #[derive(Accounts)]
 pub struct Claim<'info> {
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -26,8 +26,9 @@ pub struct Claim<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    pub associated_bonding_curve: Box<Account<'info, TokenAccount>>,
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
     pub user: Signer<'info>,
@@ -41,32 +42,76 @@ pub struct Claim<'info> {
         ],
         bump,
     )]
-    pub user_purchase: Box<Account<'info, UserPurchase>>,
+    pub user_purchase: Option<Box<Account<'info, UserPurchase>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            RECEIPT_MINT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = user,
+        associated_token::token_program = token_program,
+    )]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     #[account(
         init_if_needed,
         payer = user,
         associated_token::mint = mint,
         associated_token::authority = user,
+        associated_token::token_program = token_program,
     )]
-    pub user_token_account: Box<Account<'info, TokenAccount>>,
+    pub user_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
 pub fn claim(ctx: Context<Claim>) -> Result<()> {
     let bonding_curve = &ctx.accounts.bonding_curve;
-    let user_purchase = &mut ctx.accounts.user_purchase;
 
     // Check if migrated
     require!(bonding_curve.migrated == true, Errors::NotMigrated);
 
-    // Check if user has purchase record
-    require!(user_purchase.token_amount > 0, Errors::NoPurchaseRecord);
-
-    let token_amount = user_purchase.token_amount;
+    let token_amount;
+    if bonding_curve.receipt_enabled {
+        // Redeem the whole receipt balance
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(Errors::MissingAccount)?;
+        let user_receipt_account = ctx.accounts.user_receipt_account.as_ref().ok_or(Errors::MissingAccount)?;
+        require!(user_receipt_account.amount > 0, Errors::NoPurchaseRecord);
+
+        token_amount = user_receipt_account.amount;
+
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: receipt_mint.to_account_info(),
+                    from: user_receipt_account.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+    } else {
+        // Check if user has purchase record
+        let user_purchase = ctx.accounts.user_purchase.as_mut().ok_or(Errors::MissingAccount)?;
+        require!(user_purchase.token_amount > 0, Errors::NoPurchaseRecord);
+
+        token_amount = user_purchase.token_amount;
+
+        // Reset token_amount to 0 after claiming
+        user_purchase.token_amount = 0;
+    }
 
     // Transfer token from bonding_curve to user
     let seeds = &[
@@ -76,22 +121,21 @@ pub fn claim(ctx: Context<Claim>) -> Result<()> {
     ];
     let signer_seeds = &[&seeds[..]];
 
-    token::transfer(
+    token_interface::transfer_checked(
         CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
+            TransferChecked {
                 from: ctx.accounts.associated_bonding_curve.to_account_info().clone(),
+                mint: ctx.accounts.mint.to_account_info(),
                 to: ctx.accounts.user_token_account.to_account_info().clone(),
                 authority: bonding_curve.to_account_info(),
             },
             signer_seeds,
         ),
         token_amount,
+        ctx.accounts.mint.decimals,
     )?;
 
-    // Reset token_amount to 0 after claiming
-    user_purchase.token_amount = 0;
-
     msg!("User {} claimed {} tokens", ctx.accounts.user.key(), token_amount);
 
     emit!(ClaimEvent {
@@ -0,0 +1,379 @@
+use {
+    crate::{constants::*, errors::Errors, events::CollectPoolFeesEvent, state::*, utils::{isqrt, pool_fee_lp}},
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        memo::spl_memo,
+        token::Token,
+        token_2022::Token2022,
+        token_interface::{self, Burn, Mint, TokenAccount, TokenInterface, TransferChecked},
+    },
+    raydium_cp_swap::{
+        cpi,
+        program::RaydiumCpSwap,
+        states::{AmmConfig, ObservationState, PoolState},
+    },
+};
+
+#[derive(Accounts)]
+pub struct CollectPoolFees<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    /// CHECK: Address validated using constraint
+    #[account(
+        address = global.fee_recipient @ Errors::InvalidFeeRecipient
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Address validated using constraint
+    #[account(
+        address = bonding_curve.creator @ Errors::NotAuthorized
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+        constraint = bonding_curve.migrated == true @ Errors::NotMigrated,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCKER_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump = lp_locker.bump,
+    )]
+    pub lp_locker: Box<Account<'info, LpLocker>>,
+
+    pub cp_swap_program: Program<'info, RaydiumCpSwap>,
+
+    /// CHECK: pool vault and lp mint authority
+    #[account(
+        seeds = [
+            raydium_cp_swap::AUTH_SEED.as_bytes(),
+        ],
+        seeds::program = cp_swap_program,
+        bump,
+    )]
+    pub authority: UncheckedAccount<'info>,
+
+    #[account(address = pool_state.load()?.amm_config)]
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    #[account(
+        mut,
+        constraint = pool_state.load()?.lp_mint == lp_locker.lp_mint @ Errors::InvalidLpRecipient,
+    )]
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    #[account(
+        mut,
+        address = lp_locker.lp_mint,
+    )]
+    pub lp_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_locker,
+        associated_token::token_program = token_program,
+    )]
+    pub lp_locker_token: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = pool_state.load()?.token_0_vault,
+    )]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        address = pool_state.load()?.token_1_vault,
+    )]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        address = token_0_vault.mint,
+        mint::token_program = token_0_program,
+    )]
+    pub token_0_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        address = token_1_vault.mint,
+        mint::token_program = token_1_program,
+    )]
+    pub token_1_mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Locker accounts receiving the withdrawn fees before the split
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_0_mint,
+        associated_token::authority = lp_locker,
+        associated_token::token_program = token_0_program,
+    )]
+    pub locker_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = token_1_mint,
+        associated_token::authority = lp_locker,
+        associated_token::token_program = token_1_program,
+    )]
+    pub locker_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_0_mint,
+        token::authority = creator,
+        token::token_program = token_0_program,
+    )]
+    pub creator_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_1_mint,
+        token::authority = creator,
+        token::token_program = token_1_program,
+    )]
+    pub creator_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_0_mint,
+        token::authority = fee_recipient,
+        token::token_program = token_0_program,
+    )]
+    pub fee_recipient_token_0: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = token_1_mint,
+        token::authority = fee_recipient,
+        token::token_program = token_1_program,
+    )]
+    pub fee_recipient_token_1: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(mut, address = pool_state.load()?.observation_key)]
+    pub observation_state: AccountLoader<'info, ObservationState>,
+
+    /// Sets the buyback minimum, so only the creator or authority can collect
+    #[account(
+        mut,
+        constraint = bonding_curve.is_creator_or_authority(&payer.key(), &global) @ Errors::NotAuthorized,
+    )]
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub token_program_2022: Program<'info, Token2022>,
+    pub token_0_program: Interface<'info, TokenInterface>,
+    pub token_1_program: Interface<'info, TokenInterface>,
+    /// CHECK: memo program
+    #[account(address = spl_memo::id())]
+    pub memo_program: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+/// One token of the pool as seen by the fee split
+struct FeeSide<'info> {
+    mint: AccountInfo<'info>,
+    decimals: u8,
+    vault: AccountInfo<'info>,
+    locker_account: AccountInfo<'info>,
+    creator_account: AccountInfo<'info>,
+    fee_recipient_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    amount: u64,
+}
+
+impl<'info> FeeSide<'info> {
+    /// Split the withdrawn amount into creator, platform and buyback shares
+    fn split(&self, creator_bps: u16, buyback_bps: u16) -> (u64, u64, u64) {
+        let creator = (self.amount as u128 * creator_bps as u128 / BASE_POINTS as u128) as u64;
+        let buyback = (self.amount as u128 * buyback_bps as u128 / BASE_POINTS as u128) as u64;
+        (creator, self.amount - creator - buyback, buyback)
+    }
+
+    fn pay(&self, to: &AccountInfo<'info>, authority: &AccountInfo<'info>, amount: u64, signer_seeds: &[&[&[u8]]]) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TransferChecked {
+                    from: self.locker_account.clone(),
+                    mint: self.mint.clone(),
+                    to: to.clone(),
+                    authority: authority.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            self.decimals,
+        )
+    }
+}
+
+pub fn collect_pool_fees(
+    ctx: Context<CollectPoolFees>,
+    min_buyback_token_out: u64,
+) -> Result<()> {
+    let clock: Clock = Clock::get()?;
+
+    // Value the locked LP against the snapshot of the last collection
+    let (lp_supply, reserve_0, reserve_1) = {
+        let pool_state = ctx.accounts.pool_state.load()?;
+        let (reserve_0, reserve_1) = pool_state.vault_amount_without_fee(ctx.accounts.token_0_vault.amount, ctx.accounts.token_1_vault.amount);
+        (pool_state.lp_supply, reserve_0, reserve_1)
+    };
+    let sqrt_k = isqrt(reserve_0 as u128 * reserve_1 as u128);
+    let lp_locker = &ctx.accounts.lp_locker;
+    let fee_lp = pool_fee_lp(lp_locker.amount, lp_locker.fee_sqrt_k, lp_locker.fee_lp_supply, sqrt_k, lp_supply)?;
+    require!(fee_lp > 0, Errors::NoPoolFees);
+
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        LP_LOCKER_SEED.as_bytes(),
+        mint_key.as_ref(),
+        &[lp_locker.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Withdraw the fee portion of the liquidity into the locker
+    let balance_0 = ctx.accounts.locker_token_0.amount;
+    let balance_1 = ctx.accounts.locker_token_1.amount;
+    let cpi_accounts = cpi::accounts::Withdraw {
+        owner: ctx.accounts.lp_locker.to_account_info(),
+        authority: ctx.accounts.authority.to_account_info(),
+        pool_state: ctx.accounts.pool_state.to_account_info(),
+        owner_lp_token: ctx.accounts.lp_locker_token.to_account_info(),
+        token_0_account: ctx.accounts.locker_token_0.to_account_info(),
+        token_1_account: ctx.accounts.locker_token_1.to_account_info(),
+        token_0_vault: ctx.accounts.token_0_vault.to_account_info(),
+        token_1_vault: ctx.accounts.token_1_vault.to_account_info(),
+        token_program: ctx.accounts.token_program.to_account_info(),
+        token_program_2022: ctx.accounts.token_program_2022.to_account_info(),
+        vault_0_mint: ctx.accounts.token_0_mint.to_account_info(),
+        vault_1_mint: ctx.accounts.token_1_mint.to_account_info(),
+        lp_mint: ctx.accounts.lp_mint.to_account_info(),
+        memo_program: ctx.accounts.memo_program.to_account_info(),
+    };
+    let cpi_context = CpiContext::new_with_signer(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts, signer_seeds);
+    cpi::withdraw(cpi_context, fee_lp, 0, 0)?;
+
+    // Received amounts are net of any Token-2022 transfer fee
+    ctx.accounts.locker_token_0.reload()?;
+    ctx.accounts.locker_token_1.reload()?;
+    let side_0 = FeeSide {
+        mint: ctx.accounts.token_0_mint.to_account_info(),
+        decimals: ctx.accounts.token_0_mint.decimals,
+        vault: ctx.accounts.token_0_vault.to_account_info(),
+        locker_account: ctx.accounts.locker_token_0.to_account_info(),
+        creator_account: ctx.accounts.creator_token_0.to_account_info(),
+        fee_recipient_account: ctx.accounts.fee_recipient_token_0.to_account_info(),
+        token_program: ctx.accounts.token_0_program.to_account_info(),
+        amount: ctx.accounts.locker_token_0.amount - balance_0,
+    };
+    let side_1 = FeeSide {
+        mint: ctx.accounts.token_1_mint.to_account_info(),
+        decimals: ctx.accounts.token_1_mint.decimals,
+        vault: ctx.accounts.token_1_vault.to_account_info(),
+        locker_account: ctx.accounts.locker_token_1.to_account_info(),
+        creator_account: ctx.accounts.creator_token_1.to_account_info(),
+        fee_recipient_account: ctx.accounts.fee_recipient_token_1.to_account_info(),
+        token_program: ctx.accounts.token_1_program.to_account_info(),
+        amount: ctx.accounts.locker_token_1.amount - balance_1,
+    };
+    let token_is_0 = ctx.accounts.token_0_mint.key() == mint_key;
+    let (token_side, quote_side) = if token_is_0 { (side_0, side_1) } else { (side_1, side_0) };
+
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    let (creator_token_amount, platform_token_amount, buyback_token_amount) = token_side.split(bonding_curve.pool_fee_creator_bps, bonding_curve.pool_fee_buyback_bps);
+    let (creator_sol_amount, platform_sol_amount, buyback_sol_amount) = quote_side.split(bonding_curve.pool_fee_creator_bps, bonding_curve.pool_fee_buyback_bps);
+
+    let locker_info = ctx.accounts.lp_locker.to_account_info();
+    token_side.pay(&token_side.creator_account, &locker_info, creator_token_amount, signer_seeds)?;
+    token_side.pay(&token_side.fee_recipient_account, &locker_info, platform_token_amount, signer_seeds)?;
+    quote_side.pay(&quote_side.creator_account, &locker_info, creator_sol_amount, signer_seeds)?;
+    quote_side.pay(&quote_side.fee_recipient_account, &locker_info, platform_sol_amount, signer_seeds)?;
+
+    // Buy back the token with the quote share in the same pool
+    let mut bought_back = 0;
+    if buyback_sol_amount > 0 {
+        let token_balance = TokenAccount::try_deserialize(&mut token_side.locker_account.try_borrow_data()?.as_ref())?.amount;
+        let cpi_accounts = cpi::accounts::Swap {
+            payer: locker_info.clone(),
+            authority: ctx.accounts.authority.to_account_info(),
+            amm_config: ctx.accounts.amm_config.to_account_info(),
+            pool_state: ctx.accounts.pool_state.to_account_info(),
+            input_token_account: quote_side.locker_account.clone(),
+            output_token_account: token_side.locker_account.clone(),
+            input_vault: quote_side.vault.clone(),
+            output_vault: token_side.vault.clone(),
+            input_token_program: quote_side.token_program.clone(),
+            output_token_program: token_side.token_program.clone(),
+            input_token_mint: quote_side.mint.clone(),
+            output_token_mint: token_side.mint.clone(),
+            observation_state: ctx.accounts.observation_state.to_account_info(),
+        };
+        let cpi_context = CpiContext::new_with_signer(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts, signer_seeds);
+        cpi::swap_base_input(cpi_context, buyback_sol_amount, min_buyback_token_out)?;
+        bought_back = TokenAccount::try_deserialize(&mut token_side.locker_account.try_borrow_data()?.as_ref())?.amount - token_balance;
+    }
+
+    // Burn the token share and whatever was bought back
+    let buyback_burned = buyback_token_amount + bought_back;
+    if buyback_burned > 0 {
+        token_interface::burn(
+            CpiContext::new_with_signer(
+                token_side.token_program.clone(),
+                Burn {
+                    mint: ctx.accounts.mint.to_account_info(),
+                    from: token_side.locker_account.clone(),
+                    authority: locker_info.clone(),
+                },
+                signer_seeds,
+            ),
+            buyback_burned,
+        )?;
+    }
+
+    // The withdrawal keeps the value per LP, snapshot it for the next collection
+    let lp_locker = &mut ctx.accounts.lp_locker;
+    lp_locker.amount = lp_locker.amount.checked_sub(fee_lp).ok_or(Errors::MathOverflow)?;
+    lp_locker.fee_sqrt_k = sqrt_k;
+    lp_locker.fee_lp_supply = lp_supply;
+
+    emit!(CollectPoolFeesEvent {
+        user: ctx.accounts.payer.key(),
+        mint: mint_key,
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        lp_amount: fee_lp,
+        token_amount: token_side.amount,
+        sol_amount: quote_side.amount,
+        creator_token_amount,
+        creator_sol_amount,
+        platform_token_amount,
+        platform_sol_amount,
+        buyback_burned,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,21 +1,34 @@
 use {
-    crate::{constants::*, errors::Errors, events::*, state::{BondingCurve, Global}},
+    crate::{constants::*, errors::Errors, events::*, state::{BondingCurve, Global, LpDisposition, MigrationTarget}, utils::{scale_token_amount, token_value, validate_decimals, validate_lp_disposition, validate_schedule, validate_token_metadata}},
     anchor_lang::{prelude::*, system_program},
     anchor_spl::{
         associated_token::AssociatedToken,
         metadata::{create_metadata_accounts_v3, mpl_token_metadata::types::DataV2, CreateMetadataAccountsV3, Metadata},
-        token::{mint_to, set_authority, Mint, MintTo, SetAuthority, Token, TokenAccount},
+        token::{spl_token::native_mint, Mint, Token, TokenAccount},
+        token_interface::{mint_to, set_authority, spl_token_2022::instruction::AuthorityType, MintTo, SetAuthority},
     },
     mpl_token_metadata::accounts::Metadata as mpl_metadata,
-    spl_token::instruction::AuthorityType, std::mem::size_of,
+    std::mem::size_of,
 };
 
 #[derive(Accounts)]
+#[instruction(
+    token_name: String,
+    token_symbol: String,
+    token_uri: String,
+    token_investing_price: u64,
+    token_investing_deadline: u64,
+    investing_start_at: u64,
+    whitelisted: bool,
+    merkle_root: [u8; 32],
+    whitelist_start_at: u64,
+    decimals: u8,
+)]
 pub struct CreateToken<'info> {
     #[account(
         init,
         payer = payer,
-        mint::decimals = 6,
+        mint::decimals = decimals,
         mint::authority = mint_authority,
     )]
     pub mint: Box<Account<'info, Mint>>,
@@ -59,6 +72,34 @@ pub struct CreateToken<'info> {
     )]
     pub associated_bonding_curve: Account<'info, TokenAccount>,
 
+    /// Receipt token minted 1:1 on buy, only passed for receipt-enabled launches
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = decimals,
+        mint::authority = bonding_curve,
+        seeds = [
+            RECEIPT_MINT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub receipt_mint: Option<Box<Account<'info, Mint>>>,
+
+    /// SPL token the sale is priced and paid in, omitted for SOL launches
+    #[account(
+        constraint = quote_mint.key() != native_mint::ID @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<Box<Account<'info, Mint>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+    )]
+    pub quote_vault: Option<Box<Account<'info, TokenAccount>>>,
+
     /// CHECK: Address validated using constraint
     #[account(
         mut,
@@ -66,6 +107,7 @@ pub struct CreateToken<'info> {
     pub withdraw_recipient: UncheckedAccount<'info>,
 
     #[account(
+        mut,
         seeds = [GLOBAL_SEED.as_ref()],
         bump,
         constraint = global.initialized == true @ Errors::NotInitialized,
@@ -101,21 +143,25 @@ pub fn create_token(
     whitelisted: bool,
     merkle_root: [u8; 32],
     whitelist_start_at: u64,
+    decimals: u8,
+    migration_target: MigrationTarget,
+    lp_disposition: LpDisposition,
+    lp_lock_duration: u64,
 ) -> Result<()> {
-    require!(token_investing_price > 0 && token_investing_deadline > 0, Errors::InvalidValue);
-    require!(whitelist_start_at <= investing_start_at, Errors::InvalidValue);
-    require!(investing_start_at < token_investing_deadline, Errors::InvalidValue);
+    require!(token_investing_price > 0, Errors::InvalidValue);
     require!(ctx.accounts.global.token_total_supply > 0, Errors::InvalidValue);
-    require!(token_name.len() <= 32, Errors::NameTooLong);
-    require!(token_symbol.len() <= 10, Errors::SymbolTooLong);
-    require!(token_uri.len() <= 200, Errors::UriTooLong);
+    validate_token_metadata(&token_name, &token_symbol, &token_uri)?;
+    validate_decimals(&ctx.accounts.global, decimals)?;
+    validate_lp_disposition(&ctx.accounts.global, migration_target, lp_disposition, lp_lock_duration)?;
+    require!(ctx.accounts.quote_mint.is_some() == ctx.accounts.quote_vault.is_some(), Errors::MissingAccount);
 
     // Getting clock
     let clock: Clock = Clock::get()?;
     let now = clock.unix_timestamp.try_into().unwrap();
-    if whitelisted {
-        require!(whitelist_start_at >= now, Errors::InvalidValue);
-    }
+    validate_schedule(&ctx.accounts.global, token_investing_deadline, investing_start_at, whitelisted, whitelist_start_at, now)?;
+
+    let launch_id = ctx.accounts.global.launch_count;
+    ctx.accounts.global.launch_count = launch_id.checked_add(1).ok_or(Errors::MathOverflow)?;
 
     let global = &ctx.accounts.global;
 
@@ -154,21 +200,26 @@ pub fn create_token(
         None,
     )?;
 
-    // Mint token
-    mint_to(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.mint.to_account_info(),
-                to: ctx.accounts.associated_bonding_curve.to_account_info(),
-                authority: ctx.accounts.mint_authority.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        global.token_total_supply,
+    init_bonding_curve(
+        &mut ctx.accounts.bonding_curve,
+        global,
+        token_investing_price,
+        token_investing_deadline,
+        investing_start_at,
+        whitelisted,
+        merkle_root,
+        whitelist_start_at,
+        decimals,
     )?;
 
-    msg!("Token minted successfully.");
+    mint_launch_supply(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.associated_bonding_curve.to_account_info(),
+        ctx.accounts.mint_authority.to_account_info(),
+        ctx.accounts.bonding_curve.token_total_supply,
+        signer_seeds,
+    )?;
 
     // Transfer rent exemption amount to vault to ensure it can accept small purchases
     let rent_exempt_minimum = ctx.accounts.rent.minimum_balance(0);
@@ -185,13 +236,71 @@ pub fn create_token(
 
     msg!("Vault initialized with rent exemption.");
 
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.withdraw_recipient = ctx.accounts.withdraw_recipient.key();
+    bonding_curve.receipt_enabled = ctx.accounts.receipt_mint.is_some();
+    bonding_curve.mint = ctx.accounts.mint.key();
+    bonding_curve.creator = ctx.accounts.payer.key();
+    bonding_curve.created_at = clock.unix_timestamp;
+    bonding_curve.launch_id = launch_id;
+    bonding_curve.bump = ctx.bumps.bonding_curve;
+    bonding_curve.vault_bump = ctx.bumps.bonding_curve_vault;
+    bonding_curve.quote_mint = ctx.accounts.quote_mint.as_ref().map_or(native_mint::ID, |quote_mint| quote_mint.key());
+    bonding_curve.migration_target = migration_target;
+    bonding_curve.lp_disposition = lp_disposition;
+    bonding_curve.lp_lock_duration = lp_lock_duration;
+
+    msg!("Bonding curve state saved successfully.");
+
+    emit!(CreateEvent {
+        name: token_name.clone(),
+        symbol: token_symbol.clone(),
+        uri: token_uri.clone(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        user: ctx.accounts.payer.key(),
+        launch_id,
+        quote_mint: ctx.accounts.bonding_curve.quote_mint,
+        migration_target,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
+
+/// Mints the whole supply into the curve and revokes the mint authority.
+/// Works with both the SPL Token and Token-2022 programs.
+pub(crate) fn mint_launch_supply<'info>(
+    token_program: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    associated_bonding_curve: AccountInfo<'info>,
+    mint_authority: AccountInfo<'info>,
+    token_total_supply: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Mint token
+    mint_to(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            MintTo {
+                mint: mint.clone(),
+                to: associated_bonding_curve,
+                authority: mint_authority.clone(),
+            },
+            signer_seeds,
+        ),
+        token_total_supply,
+    )?;
+
+    msg!("Token minted successfully.");
+
     // Revoke token mint authority to prevent further minting
     set_authority(
         CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
+            token_program,
             SetAuthority {
-                current_authority: ctx.accounts.mint_authority.to_account_info(),
-                account_or_mint: ctx.accounts.mint.to_account_info(),
+                current_authority: mint_authority,
+                account_or_mint: mint,
             },
             signer_seeds,
         ),
@@ -199,36 +308,55 @@ pub fn create_token(
         None,
     )?;
 
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    Ok(())
+}
+
+/// Writes the sale parameters of a new launch into its bonding curve.
+/// Global token amounts are scaled to the launch decimals.
+pub(crate) fn init_bonding_curve(
+    bonding_curve: &mut BondingCurve,
+    global: &Global,
+    token_investing_price: u64,
+    token_investing_deadline: u64,
+    investing_start_at: u64,
+    whitelisted: bool,
+    merkle_root: [u8; 32],
+    whitelist_start_at: u64,
+    decimals: u8,
+) -> Result<()> {
+    let token_total_supply = scale_token_amount(global.token_total_supply, decimals)?;
+    let token_investing_supply = scale_token_amount(global.token_investing_supply, decimals)?;
+    let token_pool_reserve = scale_token_amount(global.token_pool_reserve, decimals)?;
+    let token_launching_price = (token_investing_price as u128)
+        .checked_mul(global.token_price_up_bps as u128)
+        .map(|x| x / BASE_POINTS as u128)
+        .and_then(|x| u64::try_from(x).ok())
+        .ok_or(Errors::InvalidPrice)?;
+
+    // The raise and the pool side must both be worth at least a lamport without overflowing
+    let sale_value = token_value(token_investing_supply, token_investing_price, decimals)
+        .map_err(|_| error!(Errors::InvalidPrice))?;
+    let pool_value = token_value(token_pool_reserve, token_launching_price, decimals)
+        .map_err(|_| error!(Errors::InvalidPrice))?;
+    require!(sale_value > 0, Errors::InvalidPrice);
+    require!(token_pool_reserve == 0 || pool_value > 0, Errors::InvalidPrice);
+
     bonding_curve.sol_reserves = 0;
-    bonding_curve.token_reserves = global.token_total_supply;
-    bonding_curve.token_total_supply = global.token_total_supply;
-    bonding_curve.token_investing_supply = global.token_investing_supply;
+    bonding_curve.token_reserves = token_total_supply;
+    bonding_curve.token_total_supply = token_total_supply;
+    bonding_curve.token_investing_supply = token_investing_supply;
     bonding_curve.token_investing_price = token_investing_price;
     bonding_curve.token_investing_deadline = token_investing_deadline;
-    bonding_curve.token_launching_price = token_investing_price * global.token_price_up_bps as u64 / BASE_POINTS;
+    bonding_curve.token_launching_price = token_launching_price;
     bonding_curve.withdraw_fee_bps = global.withdraw_fee_bps;
-    bonding_curve.withdraw_recipient = ctx.accounts.withdraw_recipient.key();
     bonding_curve.completed = false;
     bonding_curve.investing_start_at = investing_start_at;
     bonding_curve.whitelisted = whitelisted;
     bonding_curve.merkle_root = merkle_root;
     bonding_curve.whitelist_start_at = whitelist_start_at;
-    bonding_curve.token_creator_reserve = global.token_creator_reserve;
-    bonding_curve.token_platform_reserve = global.token_platform_reserve;
-    bonding_curve.token_pool_reserve = global.token_pool_reserve;
-
-    msg!("Bonding curve state saved successfully.");
-
-    emit!(CreateEvent {
-        name: token_name.clone(),
-        symbol: token_symbol.clone(),
-        uri: token_uri.clone(),
-        mint: ctx.accounts.mint.key(),
-        bonding_curve: ctx.accounts.bonding_curve.key(),
-        user: ctx.accounts.payer.key(),
-        timestamp: clock.unix_timestamp,
-    });
+    bonding_curve.token_creator_reserve = scale_token_amount(global.token_creator_reserve, decimals)?;
+    bonding_curve.token_platform_reserve = scale_token_amount(global.token_platform_reserve, decimals)?;
+    bonding_curve.token_pool_reserve = token_pool_reserve;
 
     Ok(())
 }
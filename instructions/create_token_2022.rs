@@ -0,0 +1,377 @@
+use {
+    crate::{
+        constants::*, errors::Errors, events::*,
+        instructions::create_token::{init_bonding_curve, mint_launch_supply},
+        state::{BondingCurve, Global, LpDisposition, MigrationTarget},
+        utils::{validate_decimals, validate_lp_disposition, validate_schedule, validate_token_metadata},
+    },
+    anchor_lang::{prelude::*, system_program},
+    anchor_spl::{
+        associated_token::{self, get_associated_token_address_with_program_id, AssociatedToken, Create},
+        token::{spl_token::native_mint, Mint as SplMint, Token, TokenAccount as SplTokenAccount},
+        token_2022::{
+            initialize_mint2,
+            spl_token_2022::{extension::ExtensionType, state::Mint as MintState},
+            InitializeMint2, Token2022,
+        },
+        token_2022_extensions::{
+            metadata_pointer_initialize, transfer_fee_initialize, MetadataPointerInitialize, TransferFeeInitialize,
+        },
+        token_interface::{token_metadata_initialize, Mint, TokenMetadataInitialize},
+    },
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::TokenMetadata,
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+#[instruction(
+    token_name: String,
+    token_symbol: String,
+    token_uri: String,
+    token_investing_price: u64,
+    token_investing_deadline: u64,
+    investing_start_at: u64,
+    whitelisted: bool,
+    merkle_root: [u8; 32],
+    whitelist_start_at: u64,
+    transfer_fee_bps: u16,
+    max_transfer_fee: u64,
+    decimals: u8,
+)]
+pub struct CreateToken2022<'info> {
+    /// Created in the instruction, the transfer fee extension cannot be set up by `init`
+    #[account(mut)]
+    pub mint: Signer<'info>,
+
+    /// CHECK: Address validated using constraint
+    #[account(
+        seeds = [
+            MINT_AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub mint_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = size_of::<BondingCurve>() + 8,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_VAULT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve_vault: SystemAccount<'info>,
+
+    /// CHECK: Created in the instruction once the mint exists
+    #[account(
+        mut,
+        address = get_associated_token_address_with_program_id(&bonding_curve.key(), &mint.key(), &token_program.key()),
+    )]
+    pub associated_bonding_curve: UncheckedAccount<'info>,
+
+    /// Receipt token minted 1:1 on buy, only passed for receipt-enabled launches
+    #[account(
+        init,
+        payer = payer,
+        mint::decimals = decimals,
+        mint::authority = bonding_curve,
+        mint::token_program = token_program,
+        seeds = [
+            RECEIPT_MINT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// SPL token the sale is priced and paid in, omitted for SOL launches
+    #[account(
+        constraint = quote_mint.key() != native_mint::ID @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<Box<Account<'info, SplMint>>>,
+
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub quote_vault: Option<Box<Account<'info, SplTokenAccount>>>,
+
+    /// CHECK: Address validated using constraint
+    #[account(
+        mut,
+    )]
+    pub withdraw_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    /// CHECK: Withdraw-withheld authority of the transfer fee extension
+    #[account(
+        seeds = [
+            TRANSFER_FEE_AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub transfer_fee_authority: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub quote_token_program: Option<Program<'info, Token>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn create_token_2022(
+    ctx: Context<CreateToken2022>,
+    token_name: String,
+    token_symbol: String,
+    token_uri: String,
+    token_investing_price: u64,
+    token_investing_deadline: u64,
+    investing_start_at: u64,
+    whitelisted: bool,
+    merkle_root: [u8; 32],
+    whitelist_start_at: u64,
+    transfer_fee_bps: u16,
+    max_transfer_fee: u64,
+    decimals: u8,
+    migration_target: MigrationTarget,
+    lp_disposition: LpDisposition,
+    lp_lock_duration: u64,
+) -> Result<()> {
+    require!(token_investing_price > 0, Errors::InvalidValue);
+    require!(transfer_fee_bps <= ctx.accounts.global.max_transfer_fee_bps, Errors::TransferFeeTooHigh);
+    require!(max_transfer_fee <= ctx.accounts.global.max_transfer_fee, Errors::TransferFeeTooHigh);
+    require!(ctx.accounts.global.token_total_supply > 0, Errors::InvalidValue);
+    validate_token_metadata(&token_name, &token_symbol, &token_uri)?;
+    validate_decimals(&ctx.accounts.global, decimals)?;
+    validate_lp_disposition(&ctx.accounts.global, migration_target, lp_disposition, lp_lock_duration)?;
+    // Whirlpool pools are created with the classic token program only
+    require!(migration_target != MigrationTarget::OrcaWhirlpool, Errors::InvalidMigrationTarget);
+    require!(ctx.accounts.quote_mint.is_some() == ctx.accounts.quote_vault.is_some(), Errors::MissingAccount);
+
+    // Getting clock
+    let clock: Clock = Clock::get()?;
+    let now = clock.unix_timestamp.try_into().unwrap();
+    validate_schedule(&ctx.accounts.global, token_investing_deadline, investing_start_at, whitelisted, whitelist_start_at, now)?;
+
+    let launch_id = ctx.accounts.global.launch_count;
+    ctx.accounts.global.launch_count = launch_id.checked_add(1).ok_or(Errors::MathOverflow)?;
+
+    let global = &ctx.accounts.global;
+
+    let seeds = &[
+        MINT_AUTHORITY_SEED.as_bytes(),
+        &[ctx.bumps.mint_authority],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    // Create the mint with its fixed-size extensions
+    let mut extensions = vec![ExtensionType::MetadataPointer];
+    if transfer_fee_bps > 0 {
+        extensions.push(ExtensionType::TransferFeeConfig);
+    }
+    let mint_space = ExtensionType::try_calculate_account_len::<MintState>(&extensions)?;
+    system_program::create_account(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::CreateAccount {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        ctx.accounts.rent.minimum_balance(mint_space),
+        mint_space as u64,
+        &ctx.accounts.token_program.key(),
+    )?;
+
+    metadata_pointer_initialize(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            MetadataPointerInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        Some(ctx.accounts.mint_authority.key()),
+        Some(ctx.accounts.mint.key()),
+    )?;
+
+    if transfer_fee_bps > 0 {
+        // Fees are fixed for the life of the token and only our PDA can withdraw them
+        transfer_fee_initialize(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferFeeInitialize {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            None,
+            Some(&ctx.accounts.transfer_fee_authority.key()),
+            transfer_fee_bps,
+            max_transfer_fee,
+        )?;
+    }
+
+    initialize_mint2(
+        CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            InitializeMint2 {
+                mint: ctx.accounts.mint.to_account_info(),
+            },
+        ),
+        decimals,
+        &ctx.accounts.mint_authority.key(),
+        None,
+    )?;
+
+    associated_token::create(
+        CpiContext::new(
+            ctx.accounts.associated_token_program.to_account_info(),
+            Create {
+                payer: ctx.accounts.payer.to_account_info(),
+                associated_token: ctx.accounts.associated_bonding_curve.to_account_info(),
+                authority: ctx.accounts.bonding_curve.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+        ),
+    )?;
+
+    // The metadata extension grows the mint, fund the extra rent up front
+    let token_metadata = TokenMetadata {
+        update_authority: OptionalNonZeroPubkey::try_from(Some(ctx.accounts.mint_authority.key()))?,
+        mint: ctx.accounts.mint.key(),
+        name: token_name.clone(),
+        symbol: token_symbol.clone(),
+        uri: token_uri.clone(),
+        additional_metadata: vec![],
+    };
+    let mint_info = ctx.accounts.mint.to_account_info();
+    let metadata_space = mint_info.data_len() + token_metadata.tlv_size_of()?;
+    let metadata_rent = ctx.accounts.rent.minimum_balance(metadata_space).saturating_sub(mint_info.lamports());
+    if metadata_rent > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: mint_info.clone(),
+                },
+            ),
+            metadata_rent,
+        )?;
+    }
+
+    // Create token metadata in the mint itself
+    token_metadata_initialize(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            TokenMetadataInitialize {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: mint_info.clone(),
+                metadata: mint_info.clone(),
+                mint_authority: ctx.accounts.mint_authority.to_account_info(),
+                update_authority: ctx.accounts.mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        token_name.clone(),
+        token_symbol.clone(),
+        token_uri.clone(),
+    )?;
+
+    init_bonding_curve(
+        &mut ctx.accounts.bonding_curve,
+        global,
+        token_investing_price,
+        token_investing_deadline,
+        investing_start_at,
+        whitelisted,
+        merkle_root,
+        whitelist_start_at,
+        decimals,
+    )?;
+
+    mint_launch_supply(
+        ctx.accounts.token_program.to_account_info(),
+        mint_info,
+        ctx.accounts.associated_bonding_curve.to_account_info(),
+        ctx.accounts.mint_authority.to_account_info(),
+        ctx.accounts.bonding_curve.token_total_supply,
+        signer_seeds,
+    )?;
+
+    // Transfer rent exemption amount to vault to ensure it can accept small purchases
+    let rent_exempt_minimum = ctx.accounts.rent.minimum_balance(0);
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.bonding_curve_vault.to_account_info(),
+            },
+        ),
+        rent_exempt_minimum,
+    )?;
+
+    msg!("Vault initialized with rent exemption.");
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.withdraw_recipient = ctx.accounts.withdraw_recipient.key();
+    bonding_curve.receipt_enabled = ctx.accounts.receipt_mint.is_some();
+    bonding_curve.mint = ctx.accounts.mint.key();
+    bonding_curve.creator = ctx.accounts.payer.key();
+    bonding_curve.created_at = clock.unix_timestamp;
+    bonding_curve.launch_id = launch_id;
+    bonding_curve.bump = ctx.bumps.bonding_curve;
+    bonding_curve.vault_bump = ctx.bumps.bonding_curve_vault;
+    bonding_curve.quote_mint = ctx.accounts.quote_mint.as_ref().map_or(native_mint::ID, |quote_mint| quote_mint.key());
+    bonding_curve.migration_target = migration_target;
+    bonding_curve.lp_disposition = lp_disposition;
+    bonding_curve.lp_lock_duration = lp_lock_duration;
+    bonding_curve.transfer_fee_bps = transfer_fee_bps;
+
+    msg!("Bonding curve state saved successfully.");
+
+    emit!(CreateEvent {
+        name: token_name.clone(),
+        symbol: token_symbol.clone(),
+        uri: token_uri.clone(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        user: ctx.accounts.payer.key(),
+        launch_id,
+        quote_mint: ctx.accounts.bonding_curve.quote_mint,
+        migration_target,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
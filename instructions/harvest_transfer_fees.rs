@@ -0,0 +1,174 @@
+use {
+    crate::{constants::*, errors::Errors, events::HarvestTransferFeesEvent, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token_2022::Token2022,
+        token_2022_extensions::{
+            harvest_withheld_tokens_to_mint, withdraw_withheld_tokens_from_mint, HarvestWithheldTokensToMint,
+            WithdrawWithheldTokensFromMint,
+        },
+        token_interface::{self, Mint, TokenAccount, TransferChecked},
+    },
+};
+
+#[derive(Accounts)]
+pub struct HarvestTransferFees<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    #[account(
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    /// CHECK: Withdraw-withheld authority of the transfer fee extension
+    #[account(
+        seeds = [
+            TRANSFER_FEE_AUTHORITY_SEED.as_ref(),
+        ],
+        bump,
+    )]
+    pub transfer_fee_authority: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = transfer_fee_authority,
+        associated_token::token_program = token_program,
+    )]
+    pub fee_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Address validated using constraint
+    #[account(
+        address = bonding_curve.creator @ Errors::NotAuthorized,
+    )]
+    pub creator: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_program,
+    )]
+    pub creator_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// CHECK: Address validated using constraint
+    #[account(
+        address = global.fee_recipient @ Errors::InvalidFeeRecipient,
+    )]
+    pub fee_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = fee_recipient,
+        associated_token::token_program = token_program,
+    )]
+    pub fee_recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Anyone may trigger a harvest
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token2022>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+}
+
+/// Token accounts holding withheld fees are passed as remaining accounts.
+pub fn harvest_transfer_fees<'info>(
+    ctx: Context<'_, '_, '_, 'info, HarvestTransferFees<'info>>,
+) -> Result<()> {
+    require!(ctx.accounts.bonding_curve.transfer_fee_bps > 0, Errors::InvalidValue);
+
+    // Move fees withheld in token accounts into the mint
+    if !ctx.remaining_accounts.is_empty() {
+        harvest_withheld_tokens_to_mint(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                HarvestWithheldTokensToMint {
+                    token_program_id: ctx.accounts.token_program.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                },
+            ),
+            ctx.remaining_accounts.to_vec(),
+        )?;
+    }
+
+    let seeds = &[
+        TRANSFER_FEE_AUTHORITY_SEED.as_bytes(),
+        &[ctx.bumps.transfer_fee_authority],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    withdraw_withheld_tokens_from_mint(
+        CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            WithdrawWithheldTokensFromMint {
+                token_program_id: ctx.accounts.token_program.to_account_info(),
+                mint: ctx.accounts.mint.to_account_info(),
+                destination: ctx.accounts.fee_vault.to_account_info(),
+                authority: ctx.accounts.transfer_fee_authority.to_account_info(),
+            },
+            signer_seeds,
+        ),
+    )?;
+
+    ctx.accounts.fee_vault.reload()?;
+    let harvested = ctx.accounts.fee_vault.amount;
+    let creator_amount = (harvested as u128 * ctx.accounts.global.transfer_fee_creator_share_bps as u128 / BASE_POINTS as u128) as u64;
+    let platform_amount = harvested - creator_amount;
+
+    // Payouts are transfers themselves, so their fees are withheld again for the next harvest
+    for (to, amount) in [
+        (ctx.accounts.creator_token_account.to_account_info(), creator_amount),
+        (ctx.accounts.fee_recipient_token_account.to_account_info(), platform_amount),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.fee_vault.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to,
+                    authority: ctx.accounts.transfer_fee_authority.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    emit!(HarvestTransferFeesEvent {
+        user: ctx.accounts.payer.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        creator_amount,
+        platform_amount,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
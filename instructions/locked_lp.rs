@@ -0,0 +1,27 @@
+use {
+    crate::{constants::*, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+#[derive(Accounts)]
+pub struct LockedLp<'info> {
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        seeds = [
+            LP_LOCKER_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump = lp_locker.bump,
+    )]
+    pub lp_locker: Box<Account<'info, LpLocker>>,
+}
+
+/// LP tokens of a launch currently held by its locker
+pub fn locked_lp(
+    ctx: Context<LockedLp>,
+) -> Result<u64> {
+    Ok(ctx.accounts.lp_locker.amount)
+}
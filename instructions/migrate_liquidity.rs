@@ -1,16 +1,23 @@
 use {
-    crate::{constants::*, errors::Errors, events::MigrateEvent, state::{BondingCurve, Global}},
-    anchor_lang::{prelude::*, system_program},
+    crate::{
+        constants::*,
+        errors::Errors,
+        events::{LockLpEvent, MigrateEvent},
+        migration::{finish, migration_accounts, raydium_cp, send_leftovers, settle},
+        state::{BondingCurve, Global, LpDisposition, LpLocker, MigrationTarget, PoolRegistry},
+    },
+    anchor_lang::prelude::*,
     anchor_spl::{
-        associated_token::{AssociatedToken, Create},
-        token::{self, Mint, Token, TokenAccount, Transfer},
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+        token_2022::Token2022,
         token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface},
     },
     raydium_cp_swap::{
-        cpi,
         program::RaydiumCpSwap,
         states::{AmmConfig, OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED},
     },
+    std::mem::size_of,
 };
 
 #[derive(Accounts)]
@@ -21,11 +28,16 @@ pub struct MigrateLiquidity<'info> {
     )]
     pub global: Box<Account<'info, Global>>,
 
-    #[account()]
-    pub mint: Account<'info, Mint>,
+    #[account(
+        mint::token_program = mint_token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
 
-    #[account()]
-    pub native_mint: Account<'info, Mint>,
+    /// Quote mint of the launch, the native mint for SOL launches
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Account<'info, Mint>,
 
     #[account(
         mut,
@@ -51,8 +63,17 @@ pub struct MigrateLiquidity<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = mint_token_program,
+    )]
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// Vault of an SPL quote token, only passed for launches not raising SOL
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
     )]
-    pub associated_bonding_curve: Box<Account<'info, TokenAccount>>,
+    pub quote_vault: Option<Box<Account<'info, TokenAccount>>>,
 
     /// CHECK: Address validated using constraint
     #[account(
@@ -61,9 +82,26 @@ pub struct MigrateLiquidity<'info> {
     )]
     pub lp_recipient: UncheckedAccount<'info>,
 
+    /// CHECK: Will be created during instruction execution after LP mint is initialized, only passed when the LP goes to lp_recipient
+    #[account(mut)]
+    pub lp_recipient_token_lp: Option<UncheckedAccount<'info>>,
+
+    /// Holds the LP tokens of the launch until they unlock, only passed when the LP is locked
+    #[account(
+        init,
+        payer = creator,
+        space = size_of::<LpLocker>() + 8,
+        seeds = [
+            LP_LOCKER_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub lp_locker: Option<Box<Account<'info, LpLocker>>>,
+
     /// CHECK: Will be created during instruction execution after LP mint is initialized
     #[account(mut)]
-    pub lp_recipient_token_lp: UncheckedAccount<'info>,
+    pub lp_locker_token: Option<UncheckedAccount<'info>>,
 
     pub cp_swap_program: Program<'info, RaydiumCpSwap>,
     /// Address paying to create the pool. Can be anyone
@@ -97,6 +135,19 @@ pub struct MigrateLiquidity<'info> {
     )]
     pub pool_state: UncheckedAccount<'info>,
 
+    /// Registry entry tying the pool to the launch
+    #[account(
+        init,
+        payer = creator,
+        space = size_of::<PoolRegistry>() + 8,
+        seeds = [
+            POOL_REGISTRY_SEED.as_ref(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_registry: Box<Account<'info, PoolRegistry>>,
+
     /// Token_0 mint, the key must smaller then token_1 mint.
     #[account(
         constraint = token_0_mint.key() < token_1_mint.key(),
@@ -127,16 +178,18 @@ pub struct MigrateLiquidity<'info> {
         mut,
         associated_token::mint = token_0_mint,
         associated_token::authority = creator,
+        associated_token::token_program = token_0_program,
     )]
-    pub creator_token_0: Box<Account<'info, TokenAccount>>,
+    pub creator_token_0: Box<InterfaceAccount<'info, TokenAccountInterface>>,
 
     /// creator token1 account
     #[account(
         mut,
         associated_token::mint = token_1_mint,
         associated_token::authority = creator,
+        associated_token::token_program = token_1_program,
     )]
-    pub creator_token_1: Box<Account<'info, TokenAccount>>,
+    pub creator_token_1: Box<InterfaceAccount<'info, TokenAccountInterface>>,
 
     /// CHECK: creator lp ATA token account, init by cp-swap
     #[account(mut)]
@@ -168,6 +221,24 @@ pub struct MigrateLiquidity<'info> {
     )]
     pub token_1_vault: UncheckedAccount<'info>,
 
+    /// Leftover recipient token0 account, only needed when an existing pool leaves token0 over
+    #[account(
+        mut,
+        token::mint = token_0_mint,
+        token::authority = global.leftover_recipient(),
+        token::token_program = token_0_program,
+    )]
+    pub leftover_token_0: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Leftover recipient token1 account, only needed when an existing pool leaves token1 over
+    #[account(
+        mut,
+        token::mint = token_1_mint,
+        token::authority = global.leftover_recipient(),
+        token::token_program = token_1_program,
+    )]
+    pub leftover_token_1: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
     /// create pool fee account
     #[account(
         mut,
@@ -189,6 +260,10 @@ pub struct MigrateLiquidity<'info> {
 
     /// Program to create mint account and mint tokens
     pub token_program: Program<'info, Token>,
+    /// Token program 2022, used by cp-swap deposits
+    pub token_program_2022: Program<'info, Token2022>,
+    /// Spl token program or token program 2022 owning the launched mint
+    pub mint_token_program: Interface<'info, TokenInterface>,
     /// Spl token program or token program 2022
     pub token_0_program: Interface<'info, TokenInterface>,
     /// Spl token program or token program 2022
@@ -201,155 +276,101 @@ pub struct MigrateLiquidity<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
+migration_accounts!(MigrateLiquidity, MigrateLiquidityBumps, token_0_program, token_1_program);
+
 pub fn migrate_liquidity(
     ctx: Context<MigrateLiquidity>,
 ) -> Result<()> {
-    require!(ctx.accounts.creator.key() == ctx.accounts.global.migration_caller, Errors::NotAuthorized);
-    require!(ctx.accounts.bonding_curve.completed == true, Errors::BondingCurveNotComplete);
-    require!(ctx.accounts.bonding_curve.withdrawed == true, Errors::BondingCurveNotWithdrawed);
-    require!(ctx.accounts.bonding_curve.migrated == false, Errors::BondingCurveAlreadyMigrated);
-
     // Getting clock
     let clock: Clock = Clock::get()?;
 
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
-    let token_amount = bonding_curve.token_reserves;
-    let sol_amount = bonding_curve.sol_reserves;
-
-    require!(ctx.accounts.bonding_curve_vault.lamports() >= bonding_curve.sol_reserves, Errors::BondingCurveAlreadyMigrated);
-
-    let open_time = 0;
-    let init_amount_0 ;
-    let init_amount_1;
-    let creator_token_account;
-    let creator_native_account;
-    if ctx.accounts.token_0_mint.key() == ctx.accounts.native_mint.key() {
-        init_amount_0 = sol_amount;
-        init_amount_1 = token_amount;
-        creator_native_account = &ctx.accounts.creator_token_0;
-        creator_token_account = &ctx.accounts.creator_token_1;
-    } else {
-        init_amount_0 = token_amount;
-        init_amount_1 = sol_amount;
-        creator_native_account = &ctx.accounts.creator_token_1;
-        creator_token_account = &ctx.accounts.creator_token_0;
-    }
-
-    // transfer sol from bonding_curve_vault to creator
-    let vault_seeds = &[
-        BONDING_CURVE_VAULT_SEED.as_bytes(),
-        &ctx.accounts.mint.key().to_bytes(),
-        &[ctx.bumps.bonding_curve_vault],
-    ];
-    let vault_signer_seeds = &[&vault_seeds[..]];
-    system_program::transfer(
-        CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-                from: ctx.accounts.bonding_curve_vault.to_account_info().clone(),
-                to: creator_native_account.to_account_info().clone(),
-            },
-            vault_signer_seeds,
-        ),
-        sol_amount,
-    )?;
-    // wrap sol to wsol
-    token::sync_native(CpiContext::new(
-        ctx.accounts.token_program.to_account_info(),
-        token::SyncNative {
-            account: creator_native_account.to_account_info(),
-        },
-    ))?;
-
-    // Transfer token from bonding_curve to creator
-    let seeds = &[
-        BONDING_CURVE_SEED.as_bytes(),
-        &ctx.accounts.mint.key().to_bytes(),
-        &[ctx.bumps.bonding_curve],
-    ];
-    let signer_seeds = &[&seeds[..]];
-
-    token::transfer(
-        CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            token::Transfer {
-                from: ctx.accounts.associated_bonding_curve.to_account_info().clone(),
-                to: creator_token_account.to_account_info().clone(),
-                authority: bonding_curve.to_account_info(),
-            },
-            signer_seeds,
-        ),
-        token_amount,
+    let mut settlement = settle(
+        &ctx.accounts.settlement_accounts(&ctx.bumps),
+        &ctx.accounts.global,
+        &ctx.accounts.bonding_curve,
+        MigrationTarget::RaydiumCp,
     )?;
 
-    // Create Pool
-    let cpi_accounts = cpi::accounts::Initialize {
-        creator: ctx.accounts.creator.to_account_info(),
-        amm_config: ctx.accounts.amm_config.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
-        pool_state: ctx.accounts.pool_state.to_account_info(),
-        token_0_mint: ctx.accounts.token_0_mint.to_account_info(),
-        token_1_mint: ctx.accounts.token_1_mint.to_account_info(),
-        lp_mint: ctx.accounts.lp_mint.to_account_info(),
-        creator_token_0: ctx.accounts.creator_token_0.to_account_info(),
-        creator_token_1: ctx.accounts.creator_token_1.to_account_info(),
-        creator_lp_token: ctx.accounts.creator_lp_token.to_account_info(),
-        token_0_vault: ctx.accounts.token_0_vault.to_account_info(),
-        token_1_vault: ctx.accounts.token_1_vault.to_account_info(),
-        create_pool_fee: ctx.accounts.create_pool_fee.to_account_info(),
-        observation_state: ctx.accounts.observation_state.to_account_info(),
-        token_program: ctx.accounts.token_program.to_account_info(),
-        token_0_program: ctx.accounts.token_0_program.to_account_info(),
-        token_1_program: ctx.accounts.token_1_program.to_account_info(),
-        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-        system_program: ctx.accounts.system_program.to_account_info(),
-        rent: ctx.accounts.rent.to_account_info(),
+    let (init_amount_0, init_amount_1) = settlement.pool_amounts();
+    let (lp_amount, fee_sqrt_k, fee_lp_supply) = if raydium_cp::pool_exists(ctx.accounts) {
+        // Someone created the pool first, join it at its current ratio
+        let leftover_accounts = ctx.accounts.leftover_accounts();
+        let caller_before = leftover_accounts.caller_amounts()?;
+        let deposit = raydium_cp::deposit(ctx.accounts, &settlement)?;
+        send_leftovers(&leftover_accounts, &settlement, caller_before)?;
+        // The pool keeps the open time it was created with
+        settlement.pool_open_time = deposit.open_time;
+
+        (deposit.lp_amount, deposit.sqrt_k, deposit.lp_amount)
+    } else {
+        raydium_cp::create_pool(ctx.accounts, init_amount_0, init_amount_1, settlement.pool_open_time)?;
+        let pool_sqrt_k = raydium_cp::pool_sqrt_k(ctx.accounts)?;
+
+        // Each LP starts worth one unit of sqrt(k), fee growth raises that value
+        (raydium_cp::caller_lp_amount(ctx.accounts)?, pool_sqrt_k, pool_sqrt_k as u64)
     };
-    let cpi_context = CpiContext::new(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts);
-    cpi::initialize(cpi_context, init_amount_0, init_amount_1, open_time)?;
-
-    // Transfer the lp token to lp_recipient
-    // Create LP recipient token account
-    let lp_recipient_ata = anchor_spl::associated_token::get_associated_token_address(
-        &ctx.accounts.lp_recipient.key(),
-        &ctx.accounts.lp_mint.key()
-    );
-    require!(lp_recipient_ata == ctx.accounts.lp_recipient_token_lp.key(), Errors::InvalidLpRecipient);
-    
-    // Create the associated token account if not exists
-    if ctx.accounts.lp_recipient_token_lp.data_is_empty() {
-        anchor_spl::associated_token::create(
-            CpiContext::new(
-                ctx.accounts.associated_token_program.to_account_info(),
-                Create {
-                    payer: ctx.accounts.creator.to_account_info(),
-                    associated_token: ctx.accounts.lp_recipient_token_lp.to_account_info(),
-                    authority: ctx.accounts.lp_recipient.to_account_info(),
-                    mint: ctx.accounts.lp_mint.to_account_info(),
-                    system_program: ctx.accounts.system_program.to_account_info(),
-                    token_program: ctx.accounts.token_program.to_account_info(),
-                },
-            ),
-        )?;
+
+    let mut lp_burned = 0;
+    match ctx.accounts.bonding_curve.lp_disposition {
+        LpDisposition::Lock => {
+            // Lock the lp token for lp_recipient
+            let lp_locker = ctx.accounts.lp_locker.as_ref().ok_or(Errors::MissingAccount)?;
+            let lp_locker_token = ctx.accounts.lp_locker_token.as_ref().ok_or(Errors::MissingAccount)?;
+            raydium_cp::deliver_lp(ctx.accounts, lp_locker.to_account_info(), lp_locker_token.to_account_info(), lp_amount)?;
+
+            let unlock_at = match ctx.accounts.bonding_curve.lp_lock_duration {
+                0 => 0,
+                duration => (clock.unix_timestamp as u64).checked_add(duration).ok_or(Errors::MathOverflow)?,
+            };
+
+            let bonding_curve_key = ctx.accounts.bonding_curve.key();
+            let mint_key = ctx.accounts.mint.key();
+            let lp_mint_key = ctx.accounts.lp_mint.key();
+            let lp_recipient_key = ctx.accounts.lp_recipient.key();
+            let lp_locker = ctx.accounts.lp_locker.as_mut().ok_or(Errors::MissingAccount)?;
+            lp_locker.bonding_curve = bonding_curve_key;
+            lp_locker.mint = mint_key;
+            lp_locker.lp_mint = lp_mint_key;
+            lp_locker.beneficiary = lp_recipient_key;
+            lp_locker.amount = lp_amount;
+            lp_locker.locked_at = clock.unix_timestamp;
+            lp_locker.unlock_at = unlock_at;
+            lp_locker.bump = ctx.bumps.lp_locker.ok_or(Errors::MissingAccount)?;
+            lp_locker.fee_sqrt_k = fee_sqrt_k;
+            lp_locker.fee_lp_supply = fee_lp_supply;
+
+            emit!(LockLpEvent {
+                user: ctx.accounts.creator.key(),
+                mint: mint_key,
+                bonding_curve: bonding_curve_key,
+                lp_locker: lp_locker.key(),
+                lp_mint: lp_mint_key,
+                amount: lp_amount,
+                unlock_at,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        LpDisposition::Recipient => {
+            // Transfer the lp token to lp_recipient
+            let lp_recipient_token_lp = ctx.accounts.lp_recipient_token_lp.as_ref().ok_or(Errors::MissingAccount)?;
+            raydium_cp::deliver_lp(ctx.accounts, ctx.accounts.lp_recipient.to_account_info(), lp_recipient_token_lp.to_account_info(), lp_amount)?;
+        }
+        LpDisposition::Burn => {
+            raydium_cp::burn_lp(ctx.accounts, lp_amount)?;
+            lp_burned = lp_amount;
+        }
     }
 
-    // Transfer liquidity tokens
-    let user_token_lp_account = TokenAccount::try_deserialize(&mut ctx.accounts.creator_lp_token.clone().try_borrow_data()?.as_ref())?;
-    token::transfer(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            Transfer {
-                from: ctx.accounts.creator_lp_token.to_account_info(),
-                to: ctx.accounts.lp_recipient_token_lp.to_account_info(),
-                authority: ctx.accounts.creator.to_account_info(),
-            },
-        ),
-        user_token_lp_account.amount,
-    )?;
+    ctx.accounts.pool_registry.bonding_curve = ctx.accounts.bonding_curve.key();
+    ctx.accounts.pool_registry.mint = ctx.accounts.mint.key();
 
-    bonding_curve.token_reserves = 0;
-    bonding_curve.sol_reserves = 0;
-    bonding_curve.migrated = true;
+    finish(
+        &mut ctx.accounts.bonding_curve,
+        &settlement,
+        ctx.accounts.pool_state.key(),
+        ctx.accounts.lp_mint.key(),
+        ctx.accounts.amm_config.key(),
+    );
 
     msg!("Migrate completed.");
 
@@ -357,8 +378,13 @@ pub fn migrate_liquidity(
         user: ctx.accounts.creator.key(),
         mint: ctx.accounts.mint.key(),
         bonding_curve: ctx.accounts.bonding_curve.key(),
-        token_amount: token_amount,
-        sol_amount: sol_amount,
+        token_amount: settlement.token_amount,
+        sol_amount: settlement.sol_amount,
+        lp_burned,
+        pool_open_time: settlement.pool_open_time,
+        pool: ctx.accounts.pool_state.key(),
+        lp_mint: ctx.accounts.lp_mint.key(),
+        amm_config: ctx.accounts.amm_config.key(),
         timestamp: clock.unix_timestamp,
     });
 
@@ -0,0 +1,302 @@
+use {
+    crate::{
+        constants::*,
+        errors::Errors,
+        events::MigrateEvent,
+        migration::{finish, migration_accounts, raydium_clmm, send_leftovers, settle},
+        state::{BondingCurve, Global, MigrationTarget},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::AssociatedToken,
+        token::{Mint, Token, TokenAccount},
+        token_2022::Token2022,
+        token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface},
+    },
+    raydium_amm_v3::{
+        program::AmmV3,
+        states::{AmmConfig, OBSERVATION_SEED, POOL_SEED, POOL_TICK_ARRAY_BITMAP_SEED, POOL_VAULT_SEED},
+    },
+};
+
+#[derive(Accounts)]
+pub struct MigrateLiquidityClmm<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(
+        mint::token_program = mint_token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Quote mint of the launch, the native mint for SOL launches
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_VAULT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = mint_token_program,
+    )]
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// Vault of an SPL quote token, only passed for launches not raising SOL
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+    )]
+    pub quote_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Owner of the position NFT, `global.lp_recipient` as the constraint enforces
+    #[account(
+        mut,
+        address = global.lp_recipient @ Errors::InvalidLpRecipient
+    )]
+    pub lp_recipient: UncheckedAccount<'info>,
+
+    pub clmm_program: Program<'info, AmmV3>,
+    /// Address paying to create the pool and position. Can be anyone
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// Which config the pool belongs to.
+    pub amm_config: Box<Account<'info, AmmConfig>>,
+
+    /// CHECK: Initialize an account to store the pool state, init by clmm
+    #[account(
+        mut,
+        seeds = [
+            POOL_SEED.as_bytes(),
+            amm_config.key().as_ref(),
+            token_0_mint.key().as_ref(),
+            token_1_mint.key().as_ref(),
+        ],
+        seeds::program = clmm_program,
+        bump,
+    )]
+    pub pool_state: UncheckedAccount<'info>,
+
+    /// Token_0 mint, the key must smaller then token_1 mint.
+    #[account(
+        constraint = token_0_mint.key() < token_1_mint.key(),
+        mint::token_program = token_0_program,
+    )]
+    pub token_0_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Token_1 mint, the key must grater then token_0 mint.
+    #[account(
+        mint::token_program = token_1_program,
+    )]
+    pub token_1_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// payer token0 account
+    #[account(
+        mut,
+        associated_token::mint = token_0_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_0_program,
+    )]
+    pub creator_token_0: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// creator token1 account
+    #[account(
+        mut,
+        associated_token::mint = token_1_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_1_program,
+    )]
+    pub creator_token_1: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// CHECK: Token_0 vault for the pool, init by clmm
+    #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            token_0_mint.key().as_ref()
+        ],
+        seeds::program = clmm_program,
+        bump,
+    )]
+    pub token_0_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Token_1 vault for the pool, init by clmm
+    #[account(
+        mut,
+        seeds = [
+            POOL_VAULT_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+            token_1_mint.key().as_ref()
+        ],
+        seeds::program = clmm_program,
+        bump,
+    )]
+    pub token_1_vault: UncheckedAccount<'info>,
+
+    /// Leftover recipient token0 account, only needed when the position leaves token0 over
+    #[account(
+        mut,
+        token::mint = token_0_mint,
+        token::authority = global.leftover_recipient(),
+        token::token_program = token_0_program,
+    )]
+    pub leftover_token_0: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Leftover recipient token1 account, only needed when the position leaves token1 over
+    #[account(
+        mut,
+        token::mint = token_1_mint,
+        token::authority = global.leftover_recipient(),
+        token::token_program = token_1_program,
+    )]
+    pub leftover_token_1: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// CHECK: an account to store oracle observations, init by clmm
+    #[account(
+        mut,
+        seeds = [
+            OBSERVATION_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        seeds::program = clmm_program,
+        bump,
+    )]
+    pub observation_state: UncheckedAccount<'info>,
+
+    /// CHECK: tick array bitmap extension of the pool, init by clmm
+    #[account(
+        mut,
+        seeds = [
+            POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(),
+            pool_state.key().as_ref(),
+        ],
+        seeds::program = clmm_program,
+        bump,
+    )]
+    pub tick_array_bitmap: UncheckedAccount<'info>,
+
+    /// Unique mint of the position NFT
+    #[account(mut)]
+    pub position_nft_mint: Signer<'info>,
+
+    /// CHECK: lp recipient position NFT account, init by clmm
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// CHECK: protocol position of the range, validated by clmm
+    #[account(mut)]
+    pub protocol_position: UncheckedAccount<'info>,
+
+    /// CHECK: tick array of the lower tick, validated by clmm
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: tick array of the upper tick, validated by clmm
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: personal position of the NFT, init by clmm
+    #[account(mut)]
+    pub personal_position: UncheckedAccount<'info>,
+
+    /// Program to create mint account and mint tokens
+    pub token_program: Program<'info, Token>,
+    /// Token program 2022 for the position NFT
+    pub token_program_2022: Program<'info, Token2022>,
+    /// Spl token program or token program 2022 owning the launched mint
+    pub mint_token_program: Interface<'info, TokenInterface>,
+    /// Spl token program or token program 2022
+    pub token_0_program: Interface<'info, TokenInterface>,
+    /// Spl token program or token program 2022
+    pub token_1_program: Interface<'info, TokenInterface>,
+    /// Program to create an ATA for receiving position NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// To create a new program account
+    pub system_program: Program<'info, System>,
+    /// Sysvar for program account
+    pub rent: Sysvar<'info, Rent>,
+}
+
+migration_accounts!(MigrateLiquidityClmm, MigrateLiquidityClmmBumps, token_0_program, token_1_program);
+
+pub fn migrate_liquidity_clmm(
+    ctx: Context<MigrateLiquidityClmm>,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    // Getting clock
+    let clock: Clock = Clock::get()?;
+
+    let settlement = settle(
+        &ctx.accounts.settlement_accounts(&ctx.bumps),
+        &ctx.accounts.global,
+        &ctx.accounts.bonding_curve,
+        MigrationTarget::RaydiumClmm,
+    )?;
+
+    // Create Pool at the launching price
+    let sqrt_price = settlement.sqrt_price_x64()?;
+    raydium_clmm::create_pool(ctx.accounts, sqrt_price, settlement.pool_open_time)?;
+
+    let leftover_accounts = ctx.accounts.leftover_accounts();
+    let caller_before = leftover_accounts.caller_amounts()?;
+
+    // Open the position, its NFT goes straight to lp_recipient
+    raydium_clmm::open_position(ctx.accounts, &settlement, sqrt_price, tick_lower_index, tick_upper_index)?;
+
+    // The liquidity is bound by one side, the rest of the other stays with the caller
+    send_leftovers(&leftover_accounts, &settlement, caller_before)?;
+
+    finish(
+        &mut ctx.accounts.bonding_curve,
+        &settlement,
+        ctx.accounts.pool_state.key(),
+        Pubkey::default(),
+        ctx.accounts.amm_config.key(),
+    );
+
+    msg!("Migrate to clmm completed.");
+
+    emit!(MigrateEvent {
+        user: ctx.accounts.creator.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        token_amount: settlement.token_amount,
+        sol_amount: settlement.sol_amount,
+        lp_burned: 0,
+        pool_open_time: settlement.pool_open_time,
+        pool: ctx.accounts.pool_state.key(),
+        lp_mint: Pubkey::default(),
+        amm_config: ctx.accounts.amm_config.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,9 @@
 use {
     crate::{constants::*, errors::Errors, events::MigrateFallbackEvent, state::{BondingCurve, Global}},
-    anchor_lang::{prelude::*, system_program},
+    anchor_lang::prelude::*,
     anchor_spl::{
-        associated_token::AssociatedToken,
-        token::{self, Mint, SyncNative, Token, TokenAccount},
+        token::{Mint, TokenAccount},
+        token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface},
     },
 };
 
@@ -15,11 +15,16 @@ pub struct MigrateLiquidityFallback<'info> {
     )]
     pub global: Box<Account<'info, Global>>,
 
-    #[account()]
-    pub mint: Account<'info, Mint>,
+    #[account(
+        mint::token_program = mint_token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
 
-    #[account()]
-    pub native_mint: Account<'info, Mint>,
+    /// Quote mint of the launch, the native mint for SOL launches
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Account<'info, Mint>,
 
     #[account(
         mut,
@@ -32,7 +37,6 @@ pub struct MigrateLiquidityFallback<'info> {
     pub bonding_curve: Box<Account<'info, BondingCurve>>,
 
     #[account(
-        mut,
         seeds = [
             BONDING_CURVE_VAULT_SEED.as_ref(),
             mint.key().as_ref(),
@@ -42,44 +46,26 @@ pub struct MigrateLiquidityFallback<'info> {
     pub bonding_curve_vault: SystemAccount<'info>,
 
     #[account(
-        mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = mint_token_program,
     )]
-    pub associated_bonding_curve: Box<Account<'info, TokenAccount>>,
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccountInterface>>,
 
-    /// CHECK: Address validated using constraint
+    /// Vault of an SPL quote token, only passed for launches not raising SOL
     #[account(
-        mut,
-        address = global.lp_recipient @ Errors::InvalidLpRecipient
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
     )]
-    pub lp_recipient: UncheckedAccount<'info>,
-
-    #[account(
-        init_if_needed,
-        payer = caller,
-        associated_token::mint = mint,
-        associated_token::authority = lp_recipient,
-    )]
-    pub lp_recipient_token: Box<Account<'info, TokenAccount>>,
-
-    #[account(
-        init_if_needed,
-        payer = caller,
-        associated_token::mint = native_mint,
-        associated_token::authority = lp_recipient,
-    )]
-    pub lp_recipient_native_token: Box<Account<'info, TokenAccount>>,
+    pub quote_vault: Option<Box<Account<'info, TokenAccount>>>,
 
     #[account(mut)]
     pub caller: Signer<'info>,
 
-    pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
-    pub associated_token_program: Program<'info, AssociatedToken>,
-    pub rent: Sysvar<'info, Rent>,
+    pub mint_token_program: Interface<'info, TokenInterface>,
 }
 
+/// Open claims without a pool, escrowing the reserves in the curve vaults until the migrate instruction of the launch's target creates its pool
 pub fn migrate_liquidity_fallback(
     ctx: Context<MigrateLiquidityFallback>,
 ) -> Result<()> {
@@ -94,63 +80,18 @@ pub fn migrate_liquidity_fallback(
     let token_amount = bonding_curve.token_reserves;
     let sol_amount = bonding_curve.sol_reserves;
 
-    require!(ctx.accounts.bonding_curve_vault.lamports() >= sol_amount, Errors::BondingCurveAlreadyMigrated);
-
-    if sol_amount > 0 {
-        let vault_seeds = &[
-            BONDING_CURVE_VAULT_SEED.as_bytes(),
-            &ctx.accounts.mint.key().to_bytes(),
-            &[ctx.bumps.bonding_curve_vault],
-        ];
-        let vault_signer_seeds = &[&vault_seeds[..]];
-
-        system_program::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.system_program.to_account_info(),
-                system_program::Transfer {
-                    from: ctx.accounts.bonding_curve_vault.to_account_info(),
-                    to: ctx.accounts.lp_recipient_native_token.to_account_info(),
-                },
-                vault_signer_seeds,
-            ),
-            sol_amount,
-        )?;
-
-        token::sync_native(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                SyncNative {
-                    account: ctx.accounts.lp_recipient_native_token.to_account_info(),
-                },
-            ),
-        )?;
-    }
+    require!(ctx.accounts.associated_bonding_curve.amount >= token_amount, Errors::TokenBalanceMismatch);
 
-    if token_amount > 0 {
-        let bonding_curve_seeds = &[
-            BONDING_CURVE_SEED.as_bytes(),
-            &ctx.accounts.mint.key().to_bytes(),
-            &[ctx.bumps.bonding_curve],
-        ];
-        let signer_seeds = &[&bonding_curve_seeds[..]];
-
-        token::transfer(
-            CpiContext::new_with_signer(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.associated_bonding_curve.to_account_info(),
-                    to: ctx.accounts.lp_recipient_token.to_account_info(),
-                    authority: bonding_curve.to_account_info(),
-                },
-                signer_seeds,
-            ),
-            token_amount,
-        )?;
+    if bonding_curve.is_sol_quote() {
+        require!(ctx.accounts.bonding_curve_vault.lamports() >= sol_amount, Errors::VaultBalanceMismatch);
+    } else {
+        let quote_vault = ctx.accounts.quote_vault.as_ref().ok_or(Errors::MissingAccount)?;
+        require!(quote_vault.amount >= sol_amount, Errors::VaultBalanceMismatch);
     }
 
-    bonding_curve.token_reserves = 0;
-    bonding_curve.sol_reserves = 0;
+    // The reserves stay recorded on the curve, so claims open while the pool waits
     bonding_curve.migrated = true;
+    bonding_curve.no_pool = true;
 
     emit!(MigrateFallbackEvent {
         user: ctx.accounts.caller.key(),
@@ -0,0 +1,233 @@
+use {
+    crate::{
+        constants::*,
+        errors::Errors,
+        events::MigrateEvent,
+        migration::{finish, meteora::{self, METEORA_DAMM_V2_PROGRAM_ID}, migration_accounts, send_leftovers, settle},
+        state::{BondingCurve, Global, MigrationTarget},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        token::{Mint, Token, TokenAccount},
+        token_2022::Token2022,
+        token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface},
+    },
+};
+
+#[derive(Accounts)]
+pub struct MigrateLiquidityMeteora<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(
+        mint::token_program = mint_token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Quote mint of the launch, the native mint for SOL launches
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_VAULT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = mint_token_program,
+    )]
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// Vault of an SPL quote token, only passed for launches not raising SOL
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+    )]
+    pub quote_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Owner of the position NFT, `global.lp_recipient` as the constraint enforces
+    #[account(
+        address = global.lp_recipient @ Errors::InvalidLpRecipient
+    )]
+    pub lp_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora DAMM v2 program
+    #[account(
+        address = METEORA_DAMM_V2_PROGRAM_ID,
+    )]
+    pub meteora_program: UncheckedAccount<'info>,
+
+    /// Address paying to create the pool. Can be anyone
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: Config the pool belongs to, validated by meteora
+    pub pool_config: UncheckedAccount<'info>,
+
+    /// CHECK: pool vault authority, validated by meteora
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Initialize an account to store the pool state, init by meteora
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: position of the position NFT, init by meteora
+    #[account(mut)]
+    pub position: UncheckedAccount<'info>,
+
+    /// Unique mint of the position NFT
+    #[account(mut)]
+    pub position_nft_mint: Signer<'info>,
+
+    /// CHECK: lp recipient position NFT account, init by meteora
+    #[account(mut)]
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// Token_0 mint, the key must smaller then token_1 mint.
+    #[account(
+        constraint = token_0_mint.key() < token_1_mint.key(),
+        mint::token_program = token_0_program,
+    )]
+    pub token_0_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Token_1 mint, the key must grater then token_0 mint.
+    #[account(
+        mint::token_program = token_1_program,
+    )]
+    pub token_1_mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// payer token0 account
+    #[account(
+        mut,
+        associated_token::mint = token_0_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_0_program,
+    )]
+    pub creator_token_0: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// creator token1 account
+    #[account(
+        mut,
+        associated_token::mint = token_1_mint,
+        associated_token::authority = creator,
+        associated_token::token_program = token_1_program,
+    )]
+    pub creator_token_1: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// CHECK: Token_0 vault for the pool, init by meteora
+    #[account(mut)]
+    pub token_0_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Token_1 vault for the pool, init by meteora
+    #[account(mut)]
+    pub token_1_vault: UncheckedAccount<'info>,
+
+    /// Leftover recipient token0 account, only needed when the position leaves token0 over
+    #[account(
+        mut,
+        token::mint = token_0_mint,
+        token::authority = global.leftover_recipient(),
+        token::token_program = token_0_program,
+    )]
+    pub leftover_token_0: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// Leftover recipient token1 account, only needed when the position leaves token1 over
+    #[account(
+        mut,
+        token::mint = token_1_mint,
+        token::authority = global.leftover_recipient(),
+        token::token_program = token_1_program,
+    )]
+    pub leftover_token_1: Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>,
+
+    /// CHECK: event authority of the meteora program, validated by meteora
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// Program to create mint account and mint tokens
+    pub token_program: Program<'info, Token>,
+    /// Token program 2022 for the position NFT
+    pub token_program_2022: Program<'info, Token2022>,
+    /// Spl token program or token program 2022 owning the launched mint
+    pub mint_token_program: Interface<'info, TokenInterface>,
+    /// Spl token program or token program 2022
+    pub token_0_program: Interface<'info, TokenInterface>,
+    /// Spl token program or token program 2022
+    pub token_1_program: Interface<'info, TokenInterface>,
+    /// To create a new program account
+    pub system_program: Program<'info, System>,
+}
+
+migration_accounts!(MigrateLiquidityMeteora, MigrateLiquidityMeteoraBumps, token_0_program, token_1_program);
+
+pub fn migrate_liquidity_meteora(
+    ctx: Context<MigrateLiquidityMeteora>,
+) -> Result<()> {
+    // Getting clock
+    let clock: Clock = Clock::get()?;
+
+    let settlement = settle(
+        &ctx.accounts.settlement_accounts(&ctx.bumps),
+        &ctx.accounts.global,
+        &ctx.accounts.bonding_curve,
+        MigrationTarget::Meteora,
+    )?;
+
+    let leftover_accounts = ctx.accounts.leftover_accounts();
+    let caller_before = leftover_accounts.caller_amounts()?;
+
+    // Create Pool at the launching price, the position NFT goes straight to lp_recipient
+    meteora::create_pool(ctx.accounts, &settlement)?;
+
+    // The liquidity is bound by one side, the rest of the other stays with the caller
+    send_leftovers(&leftover_accounts, &settlement, caller_before)?;
+
+    finish(
+        &mut ctx.accounts.bonding_curve,
+        &settlement,
+        ctx.accounts.pool.key(),
+        Pubkey::default(),
+        ctx.accounts.pool_config.key(),
+    );
+
+    msg!("Migrate to meteora completed.");
+
+    emit!(MigrateEvent {
+        user: ctx.accounts.creator.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        token_amount: settlement.token_amount,
+        sol_amount: settlement.sol_amount,
+        lp_burned: 0,
+        pool_open_time: settlement.pool_open_time,
+        pool: ctx.accounts.pool.key(),
+        lp_mint: Pubkey::default(),
+        amm_config: ctx.accounts.pool_config.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,261 @@
+use {
+    crate::{
+        constants::*,
+        errors::Errors,
+        events::MigrateEvent,
+        migration::{finish, migration_accounts, orca_whirlpool, send_leftovers, settle},
+        state::{BondingCurve, Global, MigrationTarget},
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::{get_associated_token_address, AssociatedToken},
+        token::{Mint, Token, TokenAccount},
+        token_interface::{Mint as MintInterface, TokenAccount as TokenAccountInterface, TokenInterface},
+    },
+    whirlpool_cpi::program::Whirlpool,
+};
+
+#[derive(Accounts)]
+#[instruction(
+    tick_spacing: u16,
+)]
+pub struct MigrateLiquidityWhirlpool<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(
+        mint::token_program = mint_token_program,
+    )]
+    pub mint: Box<InterfaceAccount<'info, MintInterface>>,
+
+    /// Quote mint of the launch, the native mint for SOL launches
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_VAULT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = mint_token_program,
+    )]
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccountInterface>>,
+
+    /// Vault of an SPL quote token, only passed for launches not raising SOL
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+    )]
+    pub quote_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// CHECK: Receives the position NFT, `global.lp_recipient` as the constraint enforces
+    #[account(
+        mut,
+        address = global.lp_recipient @ Errors::InvalidLpRecipient
+    )]
+    pub lp_recipient: UncheckedAccount<'info>,
+
+    /// CHECK: lp recipient position NFT account, created during instruction execution
+    #[account(
+        mut,
+        address = get_associated_token_address(&lp_recipient.key(), &position_mint.key()) @ Errors::InvalidLpRecipient,
+    )]
+    pub lp_recipient_position_token: UncheckedAccount<'info>,
+
+    pub whirlpool_program: Program<'info, Whirlpool>,
+    /// Address paying to create the pool and position. Can be anyone
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    /// CHECK: Whirlpools config the pool belongs to, validated by whirlpool
+    pub whirlpools_config: UncheckedAccount<'info>,
+
+    /// CHECK: Fee tier of the tick spacing, validated by whirlpool
+    pub fee_tier: UncheckedAccount<'info>,
+
+    /// CHECK: Initialize an account to store the pool state, init by whirlpool
+    #[account(
+        mut,
+        seeds = [
+            b"whirlpool".as_ref(),
+            whirlpools_config.key().as_ref(),
+            token_0_mint.key().as_ref(),
+            token_1_mint.key().as_ref(),
+            tick_spacing.to_le_bytes().as_ref(),
+        ],
+        seeds::program = whirlpool_program,
+        bump,
+    )]
+    pub whirlpool: UncheckedAccount<'info>,
+
+    /// Token_0 mint, the key must smaller then token_1 mint.
+    #[account(
+        constraint = token_0_mint.key() < token_1_mint.key(),
+    )]
+    pub token_0_mint: Box<Account<'info, Mint>>,
+
+    /// Token_1 mint, the key must grater then token_0 mint.
+    pub token_1_mint: Box<Account<'info, Mint>>,
+
+    /// payer token0 account
+    #[account(
+        mut,
+        associated_token::mint = token_0_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_0: Box<Account<'info, TokenAccount>>,
+
+    /// creator token1 account
+    #[account(
+        mut,
+        associated_token::mint = token_1_mint,
+        associated_token::authority = creator,
+    )]
+    pub creator_token_1: Box<Account<'info, TokenAccount>>,
+
+    /// Leftover recipient token0 account, only needed when the position leaves token0 over
+    #[account(
+        mut,
+        token::mint = token_0_mint,
+        token::authority = global.leftover_recipient(),
+    )]
+    pub leftover_token_0: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Leftover recipient token1 account, only needed when the position leaves token1 over
+    #[account(
+        mut,
+        token::mint = token_1_mint,
+        token::authority = global.leftover_recipient(),
+    )]
+    pub leftover_token_1: Option<Box<Account<'info, TokenAccount>>>,
+
+    /// Token_0 vault for the pool, init by whirlpool
+    #[account(mut)]
+    pub token_0_vault: Signer<'info>,
+
+    /// Token_1 vault for the pool, init by whirlpool
+    #[account(mut)]
+    pub token_1_vault: Signer<'info>,
+
+    /// CHECK: tick array of the lower tick, init by whirlpool
+    #[account(mut)]
+    pub tick_array_lower: UncheckedAccount<'info>,
+
+    /// CHECK: tick array of the upper tick, init by whirlpool
+    #[account(mut)]
+    pub tick_array_upper: UncheckedAccount<'info>,
+
+    /// CHECK: position of the position mint, init by whirlpool
+    #[account(
+        mut,
+        seeds = [
+            b"position".as_ref(),
+            position_mint.key().as_ref(),
+        ],
+        seeds::program = whirlpool_program,
+        bump,
+    )]
+    pub position: UncheckedAccount<'info>,
+
+    /// Unique mint of the position NFT
+    #[account(mut)]
+    pub position_mint: Signer<'info>,
+
+    /// CHECK: creator position NFT account, init by whirlpool
+    #[account(mut)]
+    pub position_token_account: UncheckedAccount<'info>,
+
+    /// Program to create mint account and mint tokens
+    pub token_program: Program<'info, Token>,
+    /// Spl token program owning the launched mint
+    pub mint_token_program: Interface<'info, TokenInterface>,
+    /// Program to create an ATA for receiving position NFT
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// To create a new program account
+    pub system_program: Program<'info, System>,
+    /// Sysvar for program account
+    pub rent: Sysvar<'info, Rent>,
+}
+
+migration_accounts!(MigrateLiquidityWhirlpool, MigrateLiquidityWhirlpoolBumps, token_program, token_program);
+
+pub fn migrate_liquidity_whirlpool(
+    ctx: Context<MigrateLiquidityWhirlpool>,
+    tick_spacing: u16,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    // Getting clock
+    let clock: Clock = Clock::get()?;
+
+    let settlement = settle(
+        &ctx.accounts.settlement_accounts(&ctx.bumps),
+        &ctx.accounts.global,
+        &ctx.accounts.bonding_curve,
+        MigrationTarget::OrcaWhirlpool,
+    )?;
+
+    // Create Pool at the launching price
+    let sqrt_price = settlement.sqrt_price_x64()?;
+    orca_whirlpool::create_pool(ctx.accounts, ctx.bumps.whirlpool, tick_spacing, sqrt_price, tick_lower_index, tick_upper_index)?;
+
+    let leftover_accounts = ctx.accounts.leftover_accounts();
+    let caller_before = leftover_accounts.caller_amounts()?;
+
+    // Fund the position and hand its NFT to lp_recipient
+    orca_whirlpool::open_position(ctx.accounts, &settlement, ctx.bumps.position, sqrt_price, tick_lower_index, tick_upper_index)?;
+
+    // The liquidity is bound by one side, the rest of the other stays with the caller
+    send_leftovers(&leftover_accounts, &settlement, caller_before)?;
+
+    finish(
+        &mut ctx.accounts.bonding_curve,
+        &settlement,
+        ctx.accounts.whirlpool.key(),
+        Pubkey::default(),
+        ctx.accounts.whirlpools_config.key(),
+    );
+
+    msg!("Migrate to whirlpool completed.");
+
+    emit!(MigrateEvent {
+        user: ctx.accounts.creator.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        token_amount: settlement.token_amount,
+        sol_amount: settlement.sol_amount,
+        lp_burned: 0,
+        pool_open_time: settlement.pool_open_time,
+        pool: ctx.accounts.whirlpool.key(),
+        lp_mint: Pubkey::default(),
+        amm_config: ctx.accounts.whirlpools_config.key(),
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,29 +1,67 @@
 pub mod initialize;
 pub mod set_params;
 pub mod set_authority;
+pub mod resize_accounts;
+pub mod set_launch_limits;
+pub mod set_migration_params;
 pub mod create_token;
+pub mod create_token_2022;
 pub mod buy;
 pub mod sell;
 pub mod withdraw;
 pub mod migrate_liquidity;
 pub mod migrate_liquidity_fallback;
+pub mod migrate_liquidity_clmm;
+pub mod migrate_liquidity_whirlpool;
+pub mod migrate_liquidity_meteora;
 pub mod proxy_swap_base_input;
 pub mod proxy_swap_base_output;
+pub mod set_proxy_params;
+pub mod trade;
 pub mod set_migrated;
 pub mod set_merkle_root;
 pub mod claim;
+pub mod set_withdraw_recipient;
+pub mod set_paused;
+pub mod update_schedule;
+pub mod cancel_launch;
+pub mod harvest_transfer_fees;
+pub mod unlock_lp;
+pub mod locked_lp;
+pub mod set_pool_fee_split;
+pub mod collect_pool_fees;
+pub mod set_pool_open_delay;
 
 pub use initialize::*;
 pub use set_params::*;
 pub use set_authority::*;
+pub use resize_accounts::*;
+pub use set_launch_limits::*;
+pub use set_migration_params::*;
 pub use create_token::*;
+pub use create_token_2022::*;
 pub use buy::*;
 pub use sell::*;
 pub use withdraw::*;
 pub use migrate_liquidity::*;
 pub use migrate_liquidity_fallback::*;
+pub use migrate_liquidity_clmm::*;
+pub use migrate_liquidity_whirlpool::*;
+pub use migrate_liquidity_meteora::*;
 pub use proxy_swap_base_input::*;
 pub use proxy_swap_base_output::*;
+pub use set_proxy_params::*;
+pub use trade::*;
 pub use set_migrated::*;
 pub use set_merkle_root::*;
 pub use claim::*;
+pub use set_withdraw_recipient::*;
+pub use set_paused::*;
+pub use update_schedule::*;
+pub use cancel_launch::*;
+pub use harvest_transfer_fees::*;
+pub use unlock_lp::*;
+pub use locked_lp::*;
+pub use set_pool_fee_split::*;
+pub use collect_pool_fees::*;
+pub use set_pool_open_delay::*;
@@ -6,10 +6,33 @@ use raydium_cp_swap::{
     states::{AmmConfig, ObservationState, PoolState},
 };
 
+use crate::{
+    constants::{GLOBAL_SEED, POOL_REGISTRY_SEED, TEMP_WSOL_SEED},
+    errors::Errors,
+    events::ProxyTradeEvent,
+    proxy::{pay_fees, require_proxy_pool, ProxyFees, ProxySwapAccounts},
+    state::{BondingCurve, Global},
+    utils::proxy_balance,
+};
+
 #[derive(Accounts)]
 pub struct ProxySwapBaseInput<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    /// Launch that migrated to the pool, required when the pool is registered to a launch
+    #[account(
+        constraint = bonding_curve.migrated == true @ Errors::NotMigrated,
+        constraint = bonding_curve.pool == pool_state.key() @ Errors::InvalidProxyPool,
+    )]
+    pub bonding_curve: Option<Box<Account<'info, BondingCurve>>>,
+
     pub cp_swap_program: Program<'info, RaydiumCpSwap>,
     /// The user performing the swap
+    #[account(mut)]
     pub payer: Signer<'info>,
 
     /// CHECK: pool vault and lp mint authority
@@ -30,13 +53,23 @@ pub struct ProxySwapBaseInput<'info> {
     #[account(mut)]
     pub pool_state: AccountLoader<'info, PoolState>,
 
-    /// The user token account for input token
+    /// CHECK: Registry entry of the pool, empty unless a launch migrated to it
+    #[account(
+        seeds = [
+            POOL_REGISTRY_SEED.as_ref(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_registry: UncheckedAccount<'info>,
+
+    /// The user token account for input token, omitted to pay in native SOL
     #[account(mut)]
-    pub input_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub input_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
-    /// The user token account for output token
+    /// The user token account for output token, omitted to receive native SOL
     #[account(mut)]
-    pub output_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub output_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
 
     /// The vault token account for input token
     #[account(
@@ -58,20 +91,73 @@ pub struct ProxySwapBaseInput<'info> {
     /// SPL program for output token transfers
     pub output_token_program: Interface<'info, TokenInterface>,
 
-    /// The mint of input token
+    /// The mint of input token, writable to burn buybacks of the launched token
     #[account(
+      mut,
       address = input_vault.mint
     )]
     pub input_token_mint: Box<InterfaceAccount<'info, Mint>>,
 
-    /// The mint of output token
+    /// The mint of output token, writable to burn buybacks of the launched token
     #[account(
+      mut,
       address = output_vault.mint
     )]
     pub output_token_mint: Box<InterfaceAccount<'info, Mint>>,
+    /// Fee recipient account of the input token, receiving the proxy fee
+    #[account(
+        mut,
+        token::mint = input_token_mint,
+        token::authority = global.fee_recipient,
+        token::token_program = input_token_program,
+    )]
+    pub fee_recipient_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// Creator account of the input token, receiving the creator share of the surcharge
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
     /// The program account for the most recent oracle observation
     #[account(mut, address = pool_state.load()?.observation_key)]
     pub observation_state: AccountLoader<'info, ObservationState>,
+
+    /// CHECK: Temporary WSOL account of the payer, created and closed within the swap of a native SOL side
+    #[account(
+        mut,
+        seeds = [
+            TEMP_WSOL_SEED.as_ref(),
+            payer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub temp_wsol_account: Option<UncheckedAccount<'info>>,
+
+    /// Only needed to swap native SOL
+    pub system_program: Option<Program<'info, System>>,
+}
+
+impl<'info> ProxySwapBaseInput<'info> {
+    fn swap_accounts(&self, temp_wsol_bump: u8) -> ProxySwapAccounts<'info> {
+        ProxySwapAccounts {
+            payer: self.payer.to_account_info(),
+            cp_swap_program: self.cp_swap_program.to_account_info(),
+            authority: self.authority.to_account_info(),
+            amm_config: self.amm_config.to_account_info(),
+            pool_state: self.pool_state.to_account_info(),
+            input_token_account: self.input_token_account.as_ref().map(|account| account.to_account_info()),
+            output_token_account: self.output_token_account.as_ref().map(|account| account.to_account_info()),
+            input_vault: self.input_vault.to_account_info(),
+            output_vault: self.output_vault.to_account_info(),
+            input_token_program: self.input_token_program.to_account_info(),
+            output_token_program: self.output_token_program.to_account_info(),
+            input_token_mint: self.input_token_mint.to_account_info(),
+            output_token_mint: self.output_token_mint.to_account_info(),
+            observation_state: self.observation_state.to_account_info(),
+            temp_wsol_account: self.temp_wsol_account.as_ref().map(|account| account.to_account_info()),
+            temp_wsol_bump,
+            system_program: self.system_program.as_ref().map(|program| program.to_account_info()),
+        }
+    }
 }
 
 pub fn proxy_swap_base_input(
@@ -79,21 +165,48 @@ pub fn proxy_swap_base_input(
     amount_in: u64,
     minimum_amount_out: u64,
 ) -> Result<()> {
-    let cpi_accounts = cpi::accounts::Swap {
-        payer: ctx.accounts.payer.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
-        amm_config: ctx.accounts.amm_config.to_account_info(),
-        pool_state: ctx.accounts.pool_state.to_account_info(),
-        input_token_account: ctx.accounts.input_token_account.to_account_info(),
-        output_token_account: ctx.accounts.output_token_account.to_account_info(),
-        input_vault: ctx.accounts.input_vault.to_account_info(),
-        output_vault: ctx.accounts.output_vault.to_account_info(),
-        input_token_program: ctx.accounts.input_token_program.to_account_info(),
-        output_token_program: ctx.accounts.output_token_program.to_account_info(),
-        input_token_mint: ctx.accounts.input_token_mint.to_account_info(),
-        output_token_mint: ctx.accounts.output_token_mint.to_account_info(),
-        observation_state: ctx.accounts.observation_state.to_account_info(),
-    };
-    let cpi_context = CpiContext::new(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts);
-    cpi::swap_base_input(cpi_context, amount_in, minimum_amount_out)
+    require_proxy_pool(&ctx.accounts.global, &ctx.accounts.pool_registry, ctx.accounts.bonding_curve.as_deref())?;
+
+    let input_before = proxy_balance(&ctx.accounts.input_token_account, &ctx.accounts.payer)?;
+    let output_before = proxy_balance(&ctx.accounts.output_token_account, &ctx.accounts.payer)?;
+
+    // A native SOL side swaps through a temporary WSOL account, funded with the whole input when paying
+    let swap_accounts = ctx.accounts.swap_accounts(ctx.bumps.temp_wsol_account.unwrap_or_default());
+    swap_accounts.open_native_side(amount_in)?;
+    let input_token_account = swap_accounts.input_account()?;
+
+    // The proxy fee and any anti-sniping surcharge come off the input, only the rest is swapped
+    let fees = ProxyFees::new(&ctx.accounts.global, ctx.accounts.bonding_curve.as_deref(), amount_in)?;
+    pay_fees(
+        &fees,
+        &ctx.accounts.payer.to_account_info(),
+        &input_token_account,
+        &ctx.accounts.input_token_mint,
+        &ctx.accounts.input_token_program.to_account_info(),
+        &ctx.accounts.fee_recipient_token_account.to_account_info(),
+        ctx.accounts.creator_token_account.as_deref(),
+        ctx.accounts.bonding_curve.as_deref(),
+    )?;
+
+    cpi::swap_base_input(swap_accounts.swap_context()?, amount_in - fees.total(), minimum_amount_out)?;
+
+    let launch_mint = ctx.accounts.bonding_curve.as_ref().map(|bonding_curve| bonding_curve.mint);
+    swap_accounts.buy_back(&fees, launch_mint)?;
+    swap_accounts.close_native_side()?;
+
+    emit!(ProxyTradeEvent {
+        market: ctx.accounts.amm_config.key(),
+        pool: ctx.accounts.pool_state.key(),
+        user: ctx.accounts.payer.key(),
+        input_token_mint: ctx.accounts.input_token_mint.key(),
+        output_token_mint: ctx.accounts.output_token_mint.key(),
+        input_token_amount: input_before - proxy_balance(&ctx.accounts.input_token_account, &ctx.accounts.payer)?,
+        output_token_amount: proxy_balance(&ctx.accounts.output_token_account, &ctx.accounts.payer)? - output_before,
+        fee_amount: fees.fee_amount,
+        surcharge_amount: fees.surcharge_amount,
+        fee_recipient_token_account: ctx.accounts.fee_recipient_token_account.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
 }
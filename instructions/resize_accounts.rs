@@ -0,0 +1,84 @@
+use {
+    crate::{constants::*, errors::Errors, state::*, utils::resize_account},
+    anchor_lang::{prelude::*, Discriminator},
+    anchor_spl::token_interface::Mint,
+    std::mem::size_of,
+};
+
+#[derive(Accounts)]
+pub struct ResizeGlobal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Older layouts no longer deserialize, owner and discriminator checked in the handler
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        owner = crate::ID @ Errors::InvalidAccount,
+    )]
+    pub global: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ResizeBondingCurve<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    /// CHECK: Older layouts no longer deserialize, owner and discriminator checked in the handler
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+        owner = crate::ID @ Errors::InvalidAccount,
+    )]
+    pub bonding_curve: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn resize_global(
+    ctx: Context<ResizeGlobal>,
+) -> Result<()> {
+    let global = ctx.accounts.global.to_account_info();
+
+    {
+        let data = global.try_borrow_data()?;
+        require!(data.starts_with(&Global::DISCRIMINATOR), Errors::InvalidAccount);
+
+        // `initialized` and `authority` lead every layout of Global
+        let authority = data.get(9..41).ok_or(Errors::InvalidAccount)?;
+        require!(authority == ctx.accounts.payer.key().as_ref(), Errors::NotAuthorized);
+    }
+
+    resize_account(
+        &global,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        size_of::<Global>() + 8,
+    )
+}
+
+pub fn resize_bonding_curve(
+    ctx: Context<ResizeBondingCurve>,
+) -> Result<()> {
+    let bonding_curve = ctx.accounts.bonding_curve.to_account_info();
+
+    require!(bonding_curve.try_borrow_data()?.starts_with(&BondingCurve::DISCRIMINATOR), Errors::InvalidAccount);
+
+    // Only zeroed space is added, so anyone may pay to bring a curve up to date
+    resize_account(
+        &bonding_curve,
+        &ctx.accounts.payer.to_account_info(),
+        &ctx.accounts.system_program.to_account_info(),
+        size_of::<BondingCurve>() + 8,
+    )
+}
@@ -1,9 +1,10 @@
 use {
-    crate::{constants::*, errors::Errors, events::*, state::*},
-    anchor_lang::{prelude::*, system_program},
+    crate::{constants::*, errors::Errors, events::*, state::*, utils::{pay_quote_from_vault, quote_accounts}},
+    anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{Mint, Token},
+        token::{Mint as QuoteMint, Token, TokenAccount as QuoteTokenAccount},
+        token_interface::{self, Burn, Mint, TokenAccount, TokenInterface},
     },
 };
 use solana_program::clock::Clock;
@@ -17,7 +18,7 @@ pub struct Sell<'info> {
     pub global: Box<Account<'info, Global>>,
 
     #[account()]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -51,10 +52,52 @@ pub struct Sell<'info> {
         ],
         bump,
     )]
-    pub user_purchase: Box<Account<'info, UserPurchase>>,
+    pub user_purchase: Option<Box<Account<'info, UserPurchase>>>,
+
+    #[account(
+        mut,
+        seeds = [
+            RECEIPT_MINT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub receipt_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = receipt_mint,
+        associated_token::authority = payer,
+        associated_token::token_program = token_program,
+    )]
+    pub user_receipt_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// SPL quote accounts, only passed for launches not raising SOL
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<Box<Account<'info, QuoteMint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub quote_vault: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = payer,
+        token::token_program = quote_token_program,
+    )]
+    pub payer_quote_account: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    pub quote_token_program: Option<Program<'info, Token>>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     pub rent: Sysvar<'info, Rent>,
 }
@@ -71,7 +114,6 @@ pub fn sell(
     let now = clock.unix_timestamp.try_into().unwrap();
 
     let bonding_curve = &mut ctx.accounts.bonding_curve;
-    let user_purchase = &mut ctx.accounts.user_purchase;
 
     // Enhanced validations
     require!(bonding_curve.completed == false, Errors::BondingCurveComplete);
@@ -79,9 +121,6 @@ pub fn sell(
     // Selling is only allowed after the investing deadline has passed
     require!(bonding_curve.token_investing_deadline <= now, Errors::BondingCurveNotEnded);
 
-    // Check if user has enough purchase amount to sell
-    require!(user_purchase.token_amount >= amount, Errors::InsufficientBalance);
-
     let token_decimals = *&ctx.accounts.mint.decimals;
 
     let token_amount = amount;
@@ -103,10 +142,33 @@ pub fn sell(
         .checked_add(token_amount)
         .ok_or(Errors::MathOverflow)?;
 
-    // Reduce user purchase amount instead of token transfer
-    user_purchase.token_amount = user_purchase.token_amount
-        .checked_sub(token_amount)
-        .ok_or(Errors::MathOverflow)?;
+    if bonding_curve.receipt_enabled {
+        // Burn the receipts being sold back
+        let receipt_mint = ctx.accounts.receipt_mint.as_ref().ok_or(Errors::MissingAccount)?;
+        let user_receipt_account = ctx.accounts.user_receipt_account.as_ref().ok_or(Errors::MissingAccount)?;
+        require!(user_receipt_account.amount >= token_amount, Errors::InsufficientBalance);
+
+        token_interface::burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: receipt_mint.to_account_info(),
+                    from: user_receipt_account.to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            ),
+            token_amount,
+        )?;
+    } else {
+        // Check if user has enough purchase amount to sell
+        let user_purchase = ctx.accounts.user_purchase.as_mut().ok_or(Errors::MissingAccount)?;
+        require!(user_purchase.token_amount >= token_amount, Errors::InsufficientBalance);
+
+        // Reduce user purchase amount instead of token transfer
+        user_purchase.token_amount = user_purchase.token_amount
+            .checked_sub(token_amount)
+            .ok_or(Errors::MathOverflow)?;
+    }
 
     let vault_seeds = &[
         BONDING_CURVE_VAULT_SEED.as_bytes(),
@@ -115,17 +177,19 @@ pub fn sell(
     ];
     let vault_signer_seeds = &[&vault_seeds[..]];
 
-    // Return SOL to user
-    system_program::transfer(
-        CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-                from: ctx.accounts.bonding_curve_vault.to_account_info().clone(),
-                to: ctx.accounts.payer.to_account_info().clone(),
-            },
-            vault_signer_seeds,
-        ),
+    // Return SOL or quote tokens to user
+    let quote = quote_accounts(bonding_curve, &ctx.accounts.quote_mint, &ctx.accounts.quote_vault, &ctx.accounts.quote_token_program)?;
+    let to = match quote {
+        None => ctx.accounts.payer.to_account_info(),
+        Some(_) => ctx.accounts.payer_quote_account.as_ref().ok_or(Errors::MissingAccount)?.to_account_info(),
+    };
+    pay_quote_from_vault(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.bonding_curve_vault.to_account_info(),
+        quote.as_ref(),
+        to,
         sol_amount,
+        vault_signer_seeds,
     )?;
 
     emit!(TradeEvent {
@@ -0,0 +1,53 @@
+use {
+    crate::{constants::*, errors::Errors, state::*},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SetLaunchLimits<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+        constraint = global.authority == payer.key() @ Errors::NotAuthorized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_launch_limits(
+    ctx: Context<SetLaunchLimits>,
+    min_sale_duration: u64,
+    max_sale_duration: u64,
+    max_transfer_fee_bps: u16,
+    max_transfer_fee: u64,
+    transfer_fee_creator_share_bps: u16,
+    min_token_decimals: u8,
+    max_token_decimals: u8,
+    min_lp_lock_duration: u64,
+) -> Result<()> {
+    if max_sale_duration > 0 {
+        require!(min_sale_duration <= max_sale_duration, Errors::InvalidValue);
+    }
+    require!(max_transfer_fee_bps < 10000, Errors::InvalidValue);
+    require!(transfer_fee_creator_share_bps <= 10000, Errors::InvalidValue);
+    require!(min_token_decimals <= max_token_decimals, Errors::InvalidValue);
+    require!(max_token_decimals <= MAX_TOKEN_DECIMALS, Errors::InvalidDecimals);
+
+    let global = &mut ctx.accounts.global;
+    global.min_sale_duration = min_sale_duration;
+    global.max_sale_duration = max_sale_duration;
+    global.max_transfer_fee_bps = max_transfer_fee_bps;
+    global.max_transfer_fee = max_transfer_fee;
+    global.transfer_fee_creator_share_bps = transfer_fee_creator_share_bps;
+    global.min_token_decimals = min_token_decimals;
+    global.max_token_decimals = max_token_decimals;
+    global.min_lp_lock_duration = min_lp_lock_duration;
+
+    Ok(())
+}
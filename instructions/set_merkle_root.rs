@@ -1,7 +1,7 @@
 use {
     crate::{constants::*, errors::Errors, state::*},
     anchor_lang::prelude::*,
-    anchor_spl::token::Mint,
+    anchor_spl::token_interface::Mint,
 };
 
 #[derive(Accounts)]
@@ -14,7 +14,7 @@ pub struct SetMerkleRoot<'info> {
     pub global: Box<Account<'info, Global>>,
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -28,7 +28,7 @@ pub struct SetMerkleRoot<'info> {
 
     #[account(
         mut,
-        constraint = payer.key() == global.authority @ Errors::NotAuthorized,
+        constraint = bonding_curve.is_creator_or_authority(&payer.key(), &global) @ Errors::NotAuthorized,
     )]
     pub payer: Signer<'info>,
 }
@@ -42,6 +42,13 @@ pub fn set_merkle_root(
     // Only allow updating merkle root before the sale completes
     require!(bonding_curve.completed == false, Errors::BondingCurveComplete);
 
+    // The creator may only change the whitelist while it still matters
+    if ctx.accounts.payer.key() != ctx.accounts.global.authority {
+        let now: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+        require!(now < bonding_curve.sale_start_at(), Errors::SaleAlreadyStarted);
+        require!(bonding_curve.has_purchases() == false, Errors::BondingCurveHasPurchases);
+    }
+
     bonding_curve.merkle_root = new_merkle_root;
 
     msg!("Merkle root updated successfully");
@@ -1,7 +1,17 @@
 use {
-    crate::{constants::*, errors::Errors, state::*},
+    crate::{
+        constants::*,
+        errors::Errors,
+        state::*,
+        utils::{pay_quote_from_vault, price_deviation_bps, quote_accounts, transfer_fee_for},
+    },
     anchor_lang::prelude::*,
-    anchor_spl::token::Mint,
+    anchor_spl::{
+        token::{Mint as QuoteMint, Token, TokenAccount as QuoteTokenAccount},
+        token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
+    },
+    raydium_cp_swap::states::PoolState,
+    std::mem::size_of,
 };
 
 #[derive(Accounts)]
@@ -13,7 +23,7 @@ pub struct SetMigrated<'info> {
     pub global: Box<Account<'info, Global>>,
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -25,8 +35,84 @@ pub struct SetMigrated<'info> {
     )]
     pub bonding_curve: Box<Account<'info, BondingCurve>>,
 
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_VAULT_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve_vault: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
+    )]
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SPL quote accounts, only passed for launches not raising SOL
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<Box<Account<'info, QuoteMint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub quote_vault: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = caller,
+        token::token_program = quote_token_program,
+    )]
+    pub caller_quote_account: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    pub quote_token_program: Option<Program<'info, Token>>,
+
+    /// Raydium CP pool the launch trades in, proving a market exists
+    pub pool_state: AccountLoader<'info, PoolState>,
+
+    /// Registry entry tying the pool to the launch
+    #[account(
+        init,
+        payer = caller,
+        space = size_of::<PoolRegistry>() + 8,
+        seeds = [
+            POOL_REGISTRY_SEED.as_ref(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_registry: Box<Account<'info, PoolRegistry>>,
+
+    #[account(address = pool_state.load()?.token_0_vault)]
+    pub token_0_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    #[account(address = pool_state.load()?.token_1_vault)]
+    pub token_1_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+
     #[account(mut)]
     pub caller: Signer<'info>,
+
+    /// Caller account receiving the token reserves of the curve
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = caller,
+        token::token_program = token_program,
+    )]
+    pub caller_token_account: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Interface<'info, TokenInterface>,
 }
 
 pub fn set_migrated(
@@ -36,11 +122,97 @@ pub fn set_migrated(
     require!(ctx.accounts.bonding_curve.withdrawed == true, Errors::BondingCurveNotWithdrawed);
     require!(ctx.accounts.bonding_curve.migrated == false, Errors::BondingCurveAlreadyMigrated);
 
-    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    let bonding_curve = &ctx.accounts.bonding_curve;
 
     require!(bonding_curve.completed == true, Errors::BondingCurveNotComplete);
 
+    // The pool must pair the launched mint with its quote mint
+    let pool_state = ctx.accounts.pool_state.load()?;
+    let mint_key = ctx.accounts.mint.key();
+    let quote_mint = bonding_curve.quote_mint();
+    let quote_is_token_0 = pool_state.token_0_mint == quote_mint;
+    let (quote_side, token_side) = if quote_is_token_0 {
+        (pool_state.token_0_mint, pool_state.token_1_mint)
+    } else {
+        (pool_state.token_1_mint, pool_state.token_0_mint)
+    };
+    require!(quote_side == quote_mint && token_side == mint_key, Errors::InvalidPoolMints);
+
+    // and hold liquidity priced at launch
+    let (reserve_0, reserve_1) = pool_state.vault_amount_without_fee(ctx.accounts.token_0_vault.amount, ctx.accounts.token_1_vault.amount);
+    let (quote_reserve, token_reserve) = if quote_is_token_0 { (reserve_0, reserve_1) } else { (reserve_1, reserve_0) };
+    let price_tolerance_bps = ctx.accounts.global.price_tolerance_bps() as u64 + bonding_curve.transfer_fee_bps as u64;
+    let price_deviation = price_deviation_bps(quote_reserve, token_reserve, bonding_curve.token_launching_price, ctx.accounts.mint.decimals)?;
+    require!(price_deviation <= price_tolerance_bps, Errors::MigrationPriceMismatch);
+
+    // at least as deep as the reserves the curve hands over, less the transfer fee withheld moving them
+    let token_amount = bonding_curve.token_reserves;
+    let sol_amount = bonding_curve.sol_reserves;
+    let token_fee = transfer_fee_for(&ctx.accounts.mint.to_account_info(), token_amount)?;
+    require!(quote_reserve >= sol_amount, Errors::PoolReservesTooLow);
+    require!(token_reserve >= token_amount - token_fee, Errors::PoolReservesTooLow);
+
+    let pool_lp_mint = pool_state.lp_mint;
+    let pool_amm_config = pool_state.amm_config;
+    let pool_open_time = pool_state.open_time;
+    drop(pool_state);
+
+    // The caller seeded the pool off-chain, so the reserves are paid to it as a migration would
+    let vault_seeds = &[
+        BONDING_CURVE_VAULT_SEED.as_bytes(),
+        mint_key.as_ref(),
+        &[ctx.bumps.bonding_curve_vault],
+    ];
+    let vault_signer_seeds = &[&vault_seeds[..]];
+    let quote = quote_accounts(bonding_curve, &ctx.accounts.quote_mint, &ctx.accounts.quote_vault, &ctx.accounts.quote_token_program)?;
+    let quote_to = match quote {
+        None => ctx.accounts.caller.to_account_info(),
+        Some(_) => ctx.accounts.caller_quote_account.as_ref().ok_or(Errors::MissingAccount)?.to_account_info(),
+    };
+    pay_quote_from_vault(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.bonding_curve_vault.to_account_info(),
+        quote.as_ref(),
+        quote_to,
+        sol_amount,
+        vault_signer_seeds,
+    )?;
+
+    if token_amount > 0 {
+        let seeds = &[
+            BONDING_CURVE_SEED.as_bytes(),
+            mint_key.as_ref(),
+            &[ctx.bumps.bonding_curve],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        token_interface::transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.associated_bonding_curve.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
+                    to: ctx.accounts.caller_token_account.to_account_info(),
+                    authority: ctx.accounts.bonding_curve.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            token_amount,
+            ctx.accounts.mint.decimals,
+        )?;
+    }
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.token_reserves = 0;
+    bonding_curve.sol_reserves = 0;
     bonding_curve.migrated = true;
+    bonding_curve.pool = ctx.accounts.pool_state.key();
+    bonding_curve.pool_lp_mint = pool_lp_mint;
+    bonding_curve.pool_amm_config = pool_amm_config;
+    bonding_curve.pool_open_time = pool_open_time;
+
+    let pool_registry = &mut ctx.accounts.pool_registry;
+    pool_registry.bonding_curve = bonding_curve.key();
+    pool_registry.mint = mint_key;
 
     msg!("Bonding curve migrated status set to: true");
 
@@ -0,0 +1,35 @@
+use {
+    crate::{constants::*, errors::Errors, state::*},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SetMigrationParams<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+        constraint = global.authority == payer.key() @ Errors::NotAuthorized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_migration_params(
+    ctx: Context<SetMigrationParams>,
+    leftover_recipient: Pubkey,
+    price_tolerance_bps: u16,
+) -> Result<()> {
+    require!(price_tolerance_bps <= MAX_MIGRATION_PRICE_TOLERANCE_BPS, Errors::InvalidValue);
+
+    let global = &mut ctx.accounts.global;
+    global.migration_leftover_recipient = leftover_recipient;
+    global.migration_price_tolerance_bps = price_tolerance_bps;
+
+    Ok(())
+}
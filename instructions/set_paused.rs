@@ -0,0 +1,56 @@
+use {
+    crate::{constants::*, errors::Errors, events::SetPausedEvent, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+#[derive(Accounts)]
+pub struct SetPaused<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        constraint = bonding_curve.is_creator_or_authority(&payer.key(), &global) @ Errors::NotAuthorized,
+    )]
+    pub payer: Signer<'info>,
+}
+
+pub fn set_paused(
+    ctx: Context<SetPaused>,
+    paused: bool,
+) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+
+    // Pausing only halts buys, refunds through sell stay open
+    require!(bonding_curve.completed == false, Errors::BondingCurveComplete);
+
+    bonding_curve.paused = paused;
+
+    emit!(SetPausedEvent {
+        user: ctx.accounts.payer.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: bonding_curve.key(),
+        paused,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
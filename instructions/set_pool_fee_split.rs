@@ -0,0 +1,55 @@
+use {
+    crate::{constants::*, errors::Errors, events::SetPoolFeeSplitEvent, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+#[derive(Accounts)]
+pub struct SetPoolFeeSplit<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+        constraint = global.authority == payer.key() @ Errors::NotAuthorized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+}
+
+pub fn set_pool_fee_split(
+    ctx: Context<SetPoolFeeSplit>,
+    creator_bps: u16,
+    buyback_bps: u16,
+) -> Result<()> {
+    require!(creator_bps as u64 + buyback_bps as u64 <= BASE_POINTS, Errors::InvalidValue);
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+    bonding_curve.pool_fee_creator_bps = creator_bps;
+    bonding_curve.pool_fee_buyback_bps = buyback_bps;
+
+    emit!(SetPoolFeeSplitEvent {
+        user: ctx.accounts.payer.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: bonding_curve.key(),
+        creator_bps,
+        buyback_bps,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use {
+    crate::{constants::*, errors::Errors, events::SetPoolOpenDelayEvent, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+#[derive(Accounts)]
+pub struct SetPoolOpenDelay<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        constraint = bonding_curve.is_creator_or_authority(&payer.key(), &global) @ Errors::NotAuthorized,
+    )]
+    pub payer: Signer<'info>,
+}
+
+pub fn set_pool_open_delay(
+    ctx: Context<SetPoolOpenDelay>,
+    pool_open_delay: u64,
+) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+
+    require!(bonding_curve.migrated == false, Errors::BondingCurveAlreadyMigrated);
+    require!(pool_open_delay <= MAX_POOL_OPEN_DELAY, Errors::InvalidValue);
+    // Only the Raydium pools take an open time
+    if pool_open_delay > 0 {
+        require!(
+            bonding_curve.migration_target == MigrationTarget::RaydiumCp || bonding_curve.migration_target == MigrationTarget::RaydiumClmm,
+            Errors::InvalidMigrationTarget
+        );
+    }
+
+    bonding_curve.pool_open_delay = pool_open_delay;
+
+    emit!(SetPoolOpenDelayEvent {
+        user: ctx.accounts.payer.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: bonding_curve.key(),
+        pool_open_delay,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use {
+    crate::{constants::*, errors::Errors, state::*},
+    anchor_lang::prelude::*,
+};
+
+#[derive(Accounts)]
+pub struct SetProxyParams<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+        constraint = global.authority == payer.key() @ Errors::NotAuthorized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_proxy_params(
+    ctx: Context<SetProxyParams>,
+    proxy_fee_bps: u16,
+    allow_any_pool: bool,
+    surcharge_start_bps: u16,
+    surcharge_window: u64,
+    surcharge_creator_bps: u16,
+    surcharge_buyback_bps: u16,
+) -> Result<()> {
+    require!(proxy_fee_bps <= MAX_PROXY_FEE_BPS, Errors::InvalidValue);
+    require!(surcharge_start_bps <= MAX_PROXY_SURCHARGE_BPS, Errors::InvalidValue);
+    require!(surcharge_creator_bps as u64 + surcharge_buyback_bps as u64 <= BASE_POINTS, Errors::InvalidValue);
+
+    let global = &mut ctx.accounts.global;
+    global.proxy_fee_bps = proxy_fee_bps;
+    global.proxy_allow_any_pool = allow_any_pool;
+    global.proxy_surcharge_start_bps = surcharge_start_bps;
+    global.proxy_surcharge_window = surcharge_window;
+    global.proxy_surcharge_creator_bps = surcharge_creator_bps;
+    global.proxy_surcharge_buyback_bps = surcharge_buyback_bps;
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use {
+    crate::{constants::*, errors::Errors, events::SetWithdrawRecipientEvent, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+#[derive(Accounts)]
+pub struct SetWithdrawRecipient<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    /// CHECK: Any account may receive the withdrawn funds
+    #[account()]
+    pub new_withdraw_recipient: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        constraint = payer.key() == bonding_curve.creator @ Errors::NotAuthorized,
+    )]
+    pub payer: Signer<'info>,
+}
+
+pub fn set_withdraw_recipient(
+    ctx: Context<SetWithdrawRecipient>,
+) -> Result<()> {
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+
+    // Proceeds are paid out on withdraw, changing the recipient afterwards is meaningless
+    require!(bonding_curve.withdrawed == false, Errors::BondingCurveAlreadyWithdrawed);
+
+    bonding_curve.withdraw_recipient = ctx.accounts.new_withdraw_recipient.key();
+
+    emit!(SetWithdrawRecipientEvent {
+        user: ctx.accounts.payer.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: bonding_curve.key(),
+        withdraw_recipient: bonding_curve.withdraw_recipient,
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,310 @@
+use {
+    crate::{
+        constants::*,
+        errors::Errors,
+        instructions::*,
+        state::{BondingCurve, Global, TradeSide},
+        utils::proxy_balance,
+    },
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface},
+    raydium_cp_swap::{
+        program::RaydiumCpSwap,
+        states::{AmmConfig, ObservationState, PoolState},
+    },
+    std::collections::BTreeSet,
+};
+
+/// Amounts filled by a `trade`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TradeResult {
+    /// Launched tokens bought or sold
+    pub token_amount: u64,
+    /// Quote paid for a buy or received for a sell
+    pub quote_amount: u64,
+}
+
+#[derive(Accounts)]
+pub struct Trade<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account(mut)]
+    pub mint: Box<InterfaceAccount<'info, Mint>>,
+
+    /// Locates the launch and where it trades, the curve path passes it again with the `Buy` or `Sell` accounts
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Raydium CP accounts, only passed once the launch has migrated
+    pub cp_swap_program: Option<Program<'info, RaydiumCpSwap>>,
+
+    /// CHECK: pool vault and lp mint authority, validated by cp-swap
+    pub pool_authority: Option<UncheckedAccount<'info>>,
+
+    /// Config of the pool, validated by cp-swap
+    pub amm_config: Option<Box<Account<'info, AmmConfig>>>,
+
+    #[account(
+        mut,
+        constraint = pool_state.key() == bonding_curve.pool @ Errors::InvalidProxyPool,
+    )]
+    pub pool_state: Option<AccountLoader<'info, PoolState>>,
+
+    /// CHECK: Registry entry of the pool, checked by the proxy
+    #[account(
+        seeds = [
+            POOL_REGISTRY_SEED.as_ref(),
+            pool_state.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub pool_registry: Option<UncheckedAccount<'info>>,
+
+    /// Quote mint of the pool, the native mint for SOL launches
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub pool_quote_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+
+    /// Payer account of the launched token
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = payer,
+    )]
+    pub payer_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Payer account of the quote token, omitted on SOL launches to pay or receive native SOL
+    #[account(
+        mut,
+        token::authority = payer,
+    )]
+    pub payer_pool_quote_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Pool vault of the launched token, validated by cp-swap
+    #[account(mut)]
+    pub pool_token_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Pool vault of the quote token, validated by cp-swap
+    #[account(mut)]
+    pub pool_quote_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Token program of the launched mint
+    pub pool_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Token program of the quote mint
+    pub pool_quote_token_program: Option<Interface<'info, TokenInterface>>,
+
+    /// Fee recipient account of the input token, the quote for buys and the launched token for sells
+    #[account(
+        mut,
+        token::authority = global.fee_recipient,
+    )]
+    pub fee_recipient_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    /// Creator account of the input token, receiving the creator share of any surcharge
+    #[account(mut)]
+    pub creator_token_account: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+
+    #[account(mut)]
+    pub observation_state: Option<AccountLoader<'info, ObservationState>>,
+
+    /// CHECK: Temporary WSOL account of the payer, created and closed by the proxy for a native SOL quote
+    #[account(
+        mut,
+        seeds = [
+            TEMP_WSOL_SEED.as_ref(),
+            payer.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub temp_wsol_account: Option<UncheckedAccount<'info>>,
+
+    /// Only needed to trade native SOL on the pool
+    pub system_program: Option<Program<'info, System>>,
+}
+
+impl<'info> Trade<'info> {
+    /// Proxy accounts buying the launched token with quote
+    fn proxy_buy_accounts(&self) -> Result<ProxySwapBaseOutput<'info>> {
+        let quote_mint = self.pool_quote_mint.clone().ok_or(Errors::MissingAccount)?;
+        let fee_recipient_token_account = self.fee_recipient_token_account.clone().ok_or(Errors::MissingAccount)?;
+        require!(fee_recipient_token_account.mint == quote_mint.key(), Errors::InvalidFeeRecipient);
+
+        Ok(ProxySwapBaseOutput {
+            global: self.global.clone(),
+            bonding_curve: Some(self.bonding_curve.clone()),
+            cp_swap_program: self.cp_swap_program.clone().ok_or(Errors::MissingAccount)?,
+            payer: self.payer.clone(),
+            authority: self.pool_authority.clone().ok_or(Errors::MissingAccount)?,
+            amm_config: self.amm_config.clone().ok_or(Errors::MissingAccount)?,
+            pool_state: self.pool_state.clone().ok_or(Errors::MissingAccount)?,
+            pool_registry: self.pool_registry.clone().ok_or(Errors::MissingAccount)?,
+            input_token_account: self.payer_pool_quote_account.clone(),
+            output_token_account: Some(self.payer_token_account.clone().ok_or(Errors::MissingAccount)?),
+            input_vault: self.pool_quote_vault.clone().ok_or(Errors::MissingAccount)?,
+            output_vault: self.pool_token_vault.clone().ok_or(Errors::MissingAccount)?,
+            input_token_program: self.pool_quote_token_program.clone().ok_or(Errors::MissingAccount)?,
+            output_token_program: self.pool_token_program.clone().ok_or(Errors::MissingAccount)?,
+            input_token_mint: quote_mint,
+            output_token_mint: self.mint.clone(),
+            fee_recipient_token_account,
+            creator_token_account: self.creator_token_account.clone(),
+            observation_state: self.observation_state.clone().ok_or(Errors::MissingAccount)?,
+            temp_wsol_account: self.temp_wsol_account.clone(),
+            system_program: self.system_program.clone(),
+        })
+    }
+
+    /// Proxy accounts selling the launched token for quote
+    fn proxy_sell_accounts(&self) -> Result<ProxySwapBaseInput<'info>> {
+        let fee_recipient_token_account = self.fee_recipient_token_account.clone().ok_or(Errors::MissingAccount)?;
+        require!(fee_recipient_token_account.mint == self.mint.key(), Errors::InvalidFeeRecipient);
+
+        Ok(ProxySwapBaseInput {
+            global: self.global.clone(),
+            bonding_curve: Some(self.bonding_curve.clone()),
+            cp_swap_program: self.cp_swap_program.clone().ok_or(Errors::MissingAccount)?,
+            payer: self.payer.clone(),
+            authority: self.pool_authority.clone().ok_or(Errors::MissingAccount)?,
+            amm_config: self.amm_config.clone().ok_or(Errors::MissingAccount)?,
+            pool_state: self.pool_state.clone().ok_or(Errors::MissingAccount)?,
+            pool_registry: self.pool_registry.clone().ok_or(Errors::MissingAccount)?,
+            input_token_account: Some(self.payer_token_account.clone().ok_or(Errors::MissingAccount)?),
+            output_token_account: self.payer_pool_quote_account.clone(),
+            input_vault: self.pool_token_vault.clone().ok_or(Errors::MissingAccount)?,
+            output_vault: self.pool_quote_vault.clone().ok_or(Errors::MissingAccount)?,
+            input_token_program: self.pool_token_program.clone().ok_or(Errors::MissingAccount)?,
+            output_token_program: self.pool_quote_token_program.clone().ok_or(Errors::MissingAccount)?,
+            input_token_mint: self.mint.clone(),
+            output_token_mint: self.pool_quote_mint.clone().ok_or(Errors::MissingAccount)?,
+            fee_recipient_token_account,
+            creator_token_account: self.creator_token_account.clone(),
+            observation_state: self.observation_state.clone().ok_or(Errors::MissingAccount)?,
+            temp_wsol_account: self.temp_wsol_account.clone(),
+            system_program: self.system_program.clone(),
+        })
+    }
+}
+
+/// Buy or sell `amount` tokens wherever the launch trades, `limit` bounding the quote paid or received
+pub fn trade<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Trade<'info>>,
+    side: TradeSide,
+    amount: u64,
+    limit: u64,
+    merkle_proof: Option<Vec<[u8; 32]>>,
+) -> Result<TradeResult> {
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    if !bonding_curve.completed {
+        trade_on_curve(ctx, side, amount, limit, merkle_proof)
+    } else if bonding_curve.migrated && bonding_curve.pool != Pubkey::default() {
+        trade_on_pool(ctx, side, amount, limit)
+    } else {
+        err!(Errors::TradingUnavailable)
+    }
+}
+
+/// Run `buy` or `sell` on the accounts of that instruction, passed in order as the remaining accounts
+fn trade_on_curve<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Trade<'info>>,
+    side: TradeSide,
+    amount: u64,
+    limit: u64,
+    merkle_proof: Option<Vec<[u8; 32]>>,
+) -> Result<TradeResult> {
+    let token_reserves = ctx.accounts.bonding_curve.token_reserves;
+    let sol_reserves = ctx.accounts.bonding_curve.sol_reserves;
+
+    let mut infos = ctx.remaining_accounts;
+    let mut reallocs = BTreeSet::new();
+    match side {
+        TradeSide::Buy => {
+            let mut bumps = BuyBumps::default();
+            let mut buy_accounts = Buy::try_accounts(ctx.program_id, &mut infos, &[], &mut bumps, &mut reallocs)?;
+            require!(buy_accounts.bonding_curve.key() == ctx.accounts.bonding_curve.key(), Errors::InvalidValue);
+            buy(Context::new(ctx.program_id, &mut buy_accounts, infos, bumps), amount, limit, merkle_proof)?;
+            buy_accounts.exit(ctx.program_id)?;
+        }
+        TradeSide::Sell => {
+            let mut bumps = SellBumps::default();
+            let mut sell_accounts = Sell::try_accounts(ctx.program_id, &mut infos, &[], &mut bumps, &mut reallocs)?;
+            require!(sell_accounts.bonding_curve.key() == ctx.accounts.bonding_curve.key(), Errors::InvalidValue);
+            sell(Context::new(ctx.program_id, &mut sell_accounts, infos, bumps), amount, limit)?;
+            sell_accounts.exit(ctx.program_id)?;
+        }
+    }
+
+    // Pick up the trade so the curve account does not write stale data on exit
+    ctx.accounts.bonding_curve.reload()?;
+
+    let bonding_curve = &ctx.accounts.bonding_curve;
+    Ok(TradeResult {
+        token_amount: token_reserves.abs_diff(bonding_curve.token_reserves),
+        quote_amount: sol_reserves.abs_diff(bonding_curve.sol_reserves),
+    })
+}
+
+fn trade_on_pool<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Trade<'info>>,
+    side: TradeSide,
+    amount: u64,
+    limit: u64,
+) -> Result<TradeResult> {
+    let payer_token_account = ctx.accounts.payer_token_account.as_ref().ok_or(Errors::MissingAccount)?;
+    let token_before = payer_token_account.amount;
+    let quote_before = proxy_balance(&ctx.accounts.payer_pool_quote_account, &ctx.accounts.payer)?;
+
+    match side {
+        TradeSide::Buy => {
+            let mut proxy_accounts = ctx.accounts.proxy_buy_accounts()?;
+            proxy_swap_base_output(
+                Context::new(
+                    ctx.program_id,
+                    &mut proxy_accounts,
+                    ctx.remaining_accounts,
+                    ProxySwapBaseOutputBumps { temp_wsol_account: ctx.bumps.temp_wsol_account, ..Default::default() },
+                ),
+                limit,
+                amount,
+            )?;
+        }
+        TradeSide::Sell => {
+            let mut proxy_accounts = ctx.accounts.proxy_sell_accounts()?;
+            proxy_swap_base_input(
+                Context::new(
+                    ctx.program_id,
+                    &mut proxy_accounts,
+                    ctx.remaining_accounts,
+                    ProxySwapBaseInputBumps { temp_wsol_account: ctx.bumps.temp_wsol_account, ..Default::default() },
+                ),
+                amount,
+                limit,
+            )?;
+        }
+    }
+
+    let payer_token_account = ctx.accounts.payer_token_account.as_mut().ok_or(Errors::MissingAccount)?;
+    payer_token_account.reload()?;
+    let token_after = payer_token_account.amount;
+    let quote_after = proxy_balance(&ctx.accounts.payer_pool_quote_account, &ctx.accounts.payer)?;
+
+    Ok(TradeResult {
+        token_amount: token_before.abs_diff(token_after),
+        quote_amount: quote_before.abs_diff(quote_after),
+    })
+}
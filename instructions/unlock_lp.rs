@@ -0,0 +1,105 @@
+use {
+    crate::{constants::*, errors::Errors, events::UnlockLpEvent, state::*},
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        token::{self, Mint, Token, TokenAccount, Transfer},
+        token_interface::Mint as MintInterface,
+    },
+};
+
+#[derive(Accounts)]
+pub struct UnlockLp<'info> {
+    #[account()]
+    pub mint: InterfaceAccount<'info, MintInterface>,
+
+    #[account(
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        seeds = [
+            LP_LOCKER_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump = lp_locker.bump,
+    )]
+    pub lp_locker: Box<Account<'info, LpLocker>>,
+
+    #[account(
+        address = lp_locker.lp_mint,
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    #[account(
+        mut,
+        associated_token::mint = lp_mint,
+        associated_token::authority = lp_locker,
+    )]
+    pub lp_locker_token: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        token::mint = lp_mint,
+        token::authority = lp_locker.beneficiary,
+    )]
+    pub beneficiary_token: Box<Account<'info, TokenAccount>>,
+
+    /// Anyone can release unlocked LP, it only goes to the beneficiary
+    pub payer: Signer<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn unlock_lp(
+    ctx: Context<UnlockLp>,
+) -> Result<()> {
+    let clock: Clock = Clock::get()?;
+    let now: u64 = clock.unix_timestamp.try_into().unwrap();
+
+    let lp_locker = &mut ctx.accounts.lp_locker;
+    require!(lp_locker.is_permanent() == false && now >= lp_locker.unlock_at, Errors::LpStillLocked);
+
+    let amount = lp_locker.amount;
+    let mint_key = ctx.accounts.mint.key();
+    let seeds = &[
+        LP_LOCKER_SEED.as_bytes(),
+        mint_key.as_ref(),
+        &[lp_locker.bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    if amount > 0 {
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.lp_locker_token.to_account_info(),
+                    to: ctx.accounts.beneficiary_token.to_account_info(),
+                    authority: lp_locker.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    lp_locker.amount = 0;
+
+    emit!(UnlockLpEvent {
+        user: ctx.accounts.payer.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: ctx.accounts.bonding_curve.key(),
+        lp_locker: lp_locker.key(),
+        beneficiary: lp_locker.beneficiary,
+        amount,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
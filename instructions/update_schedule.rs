@@ -0,0 +1,69 @@
+use {
+    crate::{constants::*, errors::Errors, events::UpdateScheduleEvent, state::*, utils::validate_schedule},
+    anchor_lang::prelude::*,
+    anchor_spl::token_interface::Mint,
+};
+
+#[derive(Accounts)]
+pub struct UpdateSchedule<'info> {
+    #[account(
+        seeds = [GLOBAL_SEED.as_ref()],
+        bump,
+        constraint = global.initialized == true @ Errors::NotInitialized,
+    )]
+    pub global: Box<Account<'info, Global>>,
+
+    #[account()]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    #[account(
+        mut,
+        seeds = [
+            BONDING_CURVE_SEED.as_ref(),
+            mint.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub bonding_curve: Box<Account<'info, BondingCurve>>,
+
+    #[account(
+        mut,
+        constraint = bonding_curve.is_creator_or_authority(&payer.key(), &global) @ Errors::NotAuthorized,
+    )]
+    pub payer: Signer<'info>,
+}
+
+pub fn update_schedule(
+    ctx: Context<UpdateSchedule>,
+    token_investing_deadline: u64,
+    investing_start_at: u64,
+    whitelist_start_at: u64,
+) -> Result<()> {
+    // Getting clock
+    let clock: Clock = Clock::get()?;
+    let now = clock.unix_timestamp.try_into().unwrap();
+
+    let bonding_curve = &mut ctx.accounts.bonding_curve;
+
+    // The schedule is frozen once anyone has bought into the sale
+    require!(bonding_curve.completed == false, Errors::BondingCurveComplete);
+    require!(bonding_curve.has_purchases() == false, Errors::BondingCurveHasPurchases);
+
+    validate_schedule(&ctx.accounts.global, token_investing_deadline, investing_start_at, bonding_curve.whitelisted, whitelist_start_at, now)?;
+
+    bonding_curve.token_investing_deadline = token_investing_deadline;
+    bonding_curve.investing_start_at = investing_start_at;
+    bonding_curve.whitelist_start_at = whitelist_start_at;
+
+    emit!(UpdateScheduleEvent {
+        user: ctx.accounts.payer.key(),
+        mint: ctx.accounts.mint.key(),
+        bonding_curve: bonding_curve.key(),
+        token_investing_deadline,
+        investing_start_at,
+        whitelist_start_at,
+        timestamp: clock.unix_timestamp,
+    });
+
+    Ok(())
+}
@@ -1,9 +1,10 @@
 use {
-    crate::{constants::*, errors::Errors, state::*},
-    anchor_lang::{prelude::*, system_program},
+    crate::{constants::*, errors::Errors, state::*, utils::{pay_quote_from_vault, quote_accounts, token_value}},
+    anchor_lang::prelude::*,
     anchor_spl::{
         associated_token::AssociatedToken,
-        token::{self, Mint, Token, TokenAccount},
+        token::{Mint as QuoteMint, Token, TokenAccount as QuoteTokenAccount},
+        token_interface::{self, Mint, TokenAccount, TokenInterface, TransferChecked},
     },
 };
 
@@ -26,8 +27,9 @@ pub struct Withdraw<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = fee_recipient,
+        associated_token::token_program = token_program,
     )]
-    pub associated_fee_recipient: Box<Account<'info, TokenAccount>>,
+    pub associated_fee_recipient: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: Address validated using constraint
     #[account(
@@ -40,11 +42,12 @@ pub struct Withdraw<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = withdraw_recipient,
+        associated_token::token_program = token_program,
     )]
-    pub associated_withdraw_recipient: Box<Account<'info, TokenAccount>>,
+    pub associated_withdraw_recipient: Box<InterfaceAccount<'info, TokenAccount>>,
 
     #[account(mut)]
-    pub mint: Account<'info, Mint>,
+    pub mint: InterfaceAccount<'info, Mint>,
 
     #[account(
         mut,
@@ -70,8 +73,9 @@ pub struct Withdraw<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = bonding_curve,
+        associated_token::token_program = token_program,
     )]
-    pub associated_bonding_curve: Box<Account<'info, TokenAccount>>,
+    pub associated_bonding_curve: Box<InterfaceAccount<'info, TokenAccount>>,
 
     /// CHECK: Incinerator address
     #[account(address = crate::constants::INCINERATOR)]
@@ -81,14 +85,47 @@ pub struct Withdraw<'info> {
         mut,
         associated_token::mint = mint,
         associated_token::authority = blackhole,
+        associated_token::token_program = token_program,
     )]
-    pub associated_blackhole: Box<Account<'info, TokenAccount>>,
+    pub associated_blackhole: Box<InterfaceAccount<'info, TokenAccount>>,
+
+    /// SPL quote accounts, only passed for launches not raising SOL
+    #[account(
+        address = bonding_curve.quote_mint() @ Errors::InvalidQuoteMint,
+    )]
+    pub quote_mint: Option<Box<Account<'info, QuoteMint>>>,
+
+    #[account(
+        mut,
+        associated_token::mint = quote_mint,
+        associated_token::authority = bonding_curve_vault,
+        associated_token::token_program = quote_token_program,
+    )]
+    pub quote_vault: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = withdraw_recipient,
+        token::token_program = quote_token_program,
+    )]
+    pub withdraw_recipient_quote_account: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    #[account(
+        mut,
+        token::mint = quote_mint,
+        token::authority = fee_recipient,
+        token::token_program = quote_token_program,
+    )]
+    pub fee_recipient_quote_account: Option<Box<Account<'info, QuoteTokenAccount>>>,
+
+    pub quote_token_program: Option<Program<'info, Token>>,
 
     #[account(mut)]
     pub caller: Signer<'info>,
 
     pub system_program: Program<'info, System>,
-    pub token_program: Program<'info, Token>,
+    pub token_program: Interface<'info, TokenInterface>,
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
@@ -115,23 +152,25 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
     // Total token reduce includes creator reserve, platform reserve, and pool reserve
     let token_burn = bonding_curve.token_reserves - final_token_reserves - bonding_curve.token_creator_reserve - bonding_curve.token_platform_reserve;
 
-    let final_sol_reserves = (final_token_reserves as u128 * bonding_curve.token_launching_price as u128 / 10u128.pow(token_decimals.into())) as u64;
+    let final_sol_reserves = token_value(final_token_reserves, bonding_curve.token_launching_price, token_decimals)?;
     let sol_withdraw = bonding_curve.sol_reserves - final_sol_reserves;
     let sol_fee = sol_withdraw * bonding_curve.withdraw_fee_bps as u64 / BASE_POINTS;
     let sol_creator = sol_withdraw - sol_fee;
 
     if token_burn > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
+                TransferChecked {
                     from: ctx.accounts.associated_bonding_curve.to_account_info(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.associated_blackhole.to_account_info(),
                     authority: bonding_curve.to_account_info(),
                 },
                 signer_seeds,
             ),
             token_burn,
+            token_decimals,
         )?;
     }
 
@@ -142,60 +181,70 @@ pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
     ];
     let vault_signer_seeds = &[&vault_seeds[..]];
 
-    // Withdraw to creator
-    system_program::transfer(
-        CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-                from: ctx.accounts.bonding_curve_vault.to_account_info().clone(),
-                to: ctx.accounts.withdraw_recipient.to_account_info().clone(),
-            },
-            vault_signer_seeds,
+    let quote = quote_accounts(bonding_curve, &ctx.accounts.quote_mint, &ctx.accounts.quote_vault, &ctx.accounts.quote_token_program)?;
+    let (creator_to, fee_to) = match quote {
+        None => (
+            ctx.accounts.withdraw_recipient.to_account_info(),
+            ctx.accounts.fee_recipient.to_account_info(),
+        ),
+        Some(_) => (
+            ctx.accounts.withdraw_recipient_quote_account.as_ref().ok_or(Errors::MissingAccount)?.to_account_info(),
+            ctx.accounts.fee_recipient_quote_account.as_ref().ok_or(Errors::MissingAccount)?.to_account_info(),
         ),
+    };
+
+    // Withdraw to creator
+    pay_quote_from_vault(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.bonding_curve_vault.to_account_info(),
+        quote.as_ref(),
+        creator_to,
         sol_creator,
+        vault_signer_seeds,
     )?;
     // Withdraw fees to platform
-    system_program::transfer(
-        CpiContext::new_with_signer(
-        ctx.accounts.system_program.to_account_info(),
-        system_program::Transfer {
-                from: ctx.accounts.bonding_curve_vault.to_account_info().clone(),
-                to: ctx.accounts.fee_recipient.to_account_info().clone(),
-            },
-            vault_signer_seeds,
-        ),
+    pay_quote_from_vault(
+        &ctx.accounts.system_program.to_account_info(),
+        &ctx.accounts.bonding_curve_vault.to_account_info(),
+        quote.as_ref(),
+        fee_to,
         sol_fee,
+        vault_signer_seeds,
     )?;
 
     // Transfer reserved tokens to issuer for marketing/airdrop
     if bonding_curve.token_creator_reserve > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
+                TransferChecked {
                     from: ctx.accounts.associated_bonding_curve.to_account_info().clone(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.associated_withdraw_recipient.to_account_info(),
                     authority: bonding_curve.to_account_info(),
                 },
                 signer_seeds,
             ),
             bonding_curve.token_creator_reserve,
+            token_decimals,
         )?;
     }
 
     // Transfer platform reserved tokens to fee recipient
     if bonding_curve.token_platform_reserve > 0 {
-        token::transfer(
+        token_interface::transfer_checked(
             CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
+                TransferChecked {
                     from: ctx.accounts.associated_bonding_curve.to_account_info().clone(),
+                    mint: ctx.accounts.mint.to_account_info(),
                     to: ctx.accounts.associated_fee_recipient.to_account_info(),
                     authority: bonding_curve.to_account_info(),
                 },
                 signer_seeds,
             ),
             bonding_curve.token_platform_reserve,
+            token_decimals,
         )?;
     }
 
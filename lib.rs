@@ -4,12 +4,15 @@ use anchor_lang::prelude::*;
 
 pub mod instructions;
 use instructions::*;
+use state::{LpDisposition, MigrationTarget, TradeSide};
 
 mod constants;
 mod errors;
 mod state;
 mod events;
 mod utils;
+mod migration;
+mod proxy;
 
 declare_id!("3v8WEa92iJjbbTJRTgGzZbwDQCWMassUZmoE4kgbLUev");
 
@@ -39,6 +42,30 @@ pub mod memechef {
         instructions::set_params(ctx, fee_bps, token_price_up_bps, withdraw_fee_bps, token_total_supply, token_investing_supply, token_creator_reserve, token_platform_reserve, token_pool_reserve)
     }
 
+    /// Sets the bounds applied to launch schedules.
+    pub fn set_launch_limits(
+        ctx: Context<SetLaunchLimits>,
+        min_sale_duration: u64,
+        max_sale_duration: u64,
+        max_transfer_fee_bps: u16,
+        max_transfer_fee: u64,
+        transfer_fee_creator_share_bps: u16,
+        min_token_decimals: u8,
+        max_token_decimals: u8,
+        min_lp_lock_duration: u64,
+    ) -> Result<()> {
+        instructions::set_launch_limits(ctx, min_sale_duration, max_sale_duration, max_transfer_fee_bps, max_transfer_fee, transfer_fee_creator_share_bps, min_token_decimals, max_token_decimals, min_lp_lock_duration)
+    }
+
+    /// Sets where migration leftovers are sent and how far the pool price may stray from the launching price.
+    pub fn set_migration_params(
+        ctx: Context<SetMigrationParams>,
+        leftover_recipient: Pubkey,
+        price_tolerance_bps: u16,
+    ) -> Result<()> {
+        instructions::set_migration_params(ctx, leftover_recipient, price_tolerance_bps)
+    }
+
     /// Sets the new authority of global state.
     pub fn set_authority(
         ctx: Context<SetAuthority>,
@@ -46,6 +73,20 @@ pub mod memechef {
         instructions::set_authority(ctx)
     }
 
+    /// Grows the global state created under an older layout.
+    pub fn resize_global(
+        ctx: Context<ResizeGlobal>,
+    ) -> Result<()> {
+        instructions::resize_global(ctx)
+    }
+
+    /// Grows a bonding curve created under an older layout.
+    pub fn resize_bonding_curve(
+        ctx: Context<ResizeBondingCurve>,
+    ) -> Result<()> {
+        instructions::resize_bonding_curve(ctx)
+    }
+
     /// Creates a new coin and bonding curve.
     pub fn create_token(
         ctx: Context<CreateToken>,
@@ -58,8 +99,41 @@ pub mod memechef {
         whitelisted: bool,
         merkle_root: [u8; 32],
         whitelist_start_at: u64,
+        decimals: u8,
+        migration_target: MigrationTarget,
+        lp_disposition: LpDisposition,
+        lp_lock_duration: u64,
+    ) -> Result<()> {
+        instructions::create_token(ctx, token_name, token_symbol, token_uri, token_investing_price, token_investing_deadline, investing_start_at, whitelisted, merkle_root, whitelist_start_at, decimals, migration_target, lp_disposition, lp_lock_duration)
+    }
+
+    /// Creates a new Token-2022 coin with on-mint metadata and its bonding curve.
+    pub fn create_token_2022(
+        ctx: Context<CreateToken2022>,
+        token_name: String,
+        token_symbol: String,
+        token_uri: String,
+        token_investing_price: u64,
+        token_investing_deadline: u64,
+        investing_start_at: u64,
+        whitelisted: bool,
+        merkle_root: [u8; 32],
+        whitelist_start_at: u64,
+        transfer_fee_bps: u16,
+        max_transfer_fee: u64,
+        decimals: u8,
+        migration_target: MigrationTarget,
+        lp_disposition: LpDisposition,
+        lp_lock_duration: u64,
+    ) -> Result<()> {
+        instructions::create_token_2022(ctx, token_name, token_symbol, token_uri, token_investing_price, token_investing_deadline, investing_start_at, whitelisted, merkle_root, whitelist_start_at, transfer_fee_bps, max_transfer_fee, decimals, migration_target, lp_disposition, lp_lock_duration)
+    }
+
+    /// Sweeps withheld Token-2022 transfer fees and splits them between creator and platform.
+    pub fn harvest_transfer_fees<'info>(
+        ctx: Context<'_, '_, '_, 'info, HarvestTransferFees<'info>>,
     ) -> Result<()> {
-        instructions::create_token(ctx, token_name, token_symbol, token_uri, token_investing_price, token_investing_deadline, investing_start_at, whitelisted, merkle_root, whitelist_start_at)
+        instructions::harvest_transfer_fees(ctx)
     }
 
     /// Buys tokens from a bonding curve.
@@ -89,6 +163,7 @@ pub mod memechef {
     }
 
     /// Allows the admin to migrate liquidity once the bonding curve completes.
+    /// Each migrate instruction also completes a fallback migration of a launch targeting its venue.
     pub fn migrate_liquidity(
         ctx: Context<MigrateLiquidity>,
     ) -> Result<()> {
@@ -101,6 +176,33 @@ pub mod memechef {
         instructions::migrate_liquidity_fallback(ctx)
     }
 
+    /// Migrates liquidity into a Raydium CLMM pool at the launching price.
+    /// Pass the minimum and maximum usable ticks for a full-range position.
+    pub fn migrate_liquidity_clmm(
+        ctx: Context<MigrateLiquidityClmm>,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        instructions::migrate_liquidity_clmm(ctx, tick_lower_index, tick_upper_index)
+    }
+
+    /// Migrates liquidity into an Orca Whirlpool at the launching price.
+    pub fn migrate_liquidity_whirlpool(
+        ctx: Context<MigrateLiquidityWhirlpool>,
+        tick_spacing: u16,
+        tick_lower_index: i32,
+        tick_upper_index: i32,
+    ) -> Result<()> {
+        instructions::migrate_liquidity_whirlpool(ctx, tick_spacing, tick_lower_index, tick_upper_index)
+    }
+
+    /// Migrates liquidity into a full-range Meteora DAMM v2 pool at the launching price.
+    pub fn migrate_liquidity_meteora(
+        ctx: Context<MigrateLiquidityMeteora>,
+    ) -> Result<()> {
+        instructions::migrate_liquidity_meteora(ctx)
+    }
+
     pub fn proxy_swap_base_input(
         ctx: Context<ProxySwapBaseInput>,
         amount_in: u64,
@@ -117,6 +219,31 @@ pub mod memechef {
         instructions::proxy_swap_base_output(ctx, max_amount_in, amount_out)
     }
 
+    /// Buys or sells on the curve or, once migrated, through the proxy into the launch pool.
+    /// Curve trades pass the `buy` or `sell` accounts in order as remaining accounts.
+    pub fn trade<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Trade<'info>>,
+        side: TradeSide,
+        amount: u64,
+        limit: u64,
+        merkle_proof: Option<Vec<[u8; 32]>>,
+    ) -> Result<TradeResult> {
+        instructions::trade(ctx, side, amount, limit, merkle_proof)
+    }
+
+    /// Sets the proxy swap fee, the anti-sniping surcharge and whether any pool may be used (admin only).
+    pub fn set_proxy_params(
+        ctx: Context<SetProxyParams>,
+        proxy_fee_bps: u16,
+        allow_any_pool: bool,
+        surcharge_start_bps: u16,
+        surcharge_window: u64,
+        surcharge_creator_bps: u16,
+        surcharge_buyback_bps: u16,
+    ) -> Result<()> {
+        instructions::set_proxy_params(ctx, proxy_fee_bps, allow_any_pool, surcharge_start_bps, surcharge_window, surcharge_creator_bps, surcharge_buyback_bps)
+    }
+
     /// Sets the migrated status of a bonding curve (admin only).
     pub fn set_migrated(
         ctx: Context<SetMigrated>,
@@ -124,7 +251,7 @@ pub mod memechef {
         instructions::set_migrated(ctx)
     }
 
-    /// Updates the merkle root of a bonding curve (admin only).
+    /// Updates the merkle root of a bonding curve (creator or admin).
     pub fn set_merkle_root(
         ctx: Context<SetMerkleRoot>,
         new_merkle_root: [u8; 32],
@@ -138,4 +265,75 @@ pub mod memechef {
     ) -> Result<()> {
         instructions::claim(ctx)
     }
+
+    /// Changes the recipient of the sale proceeds (creator only).
+    pub fn set_withdraw_recipient(
+        ctx: Context<SetWithdrawRecipient>,
+    ) -> Result<()> {
+        instructions::set_withdraw_recipient(ctx)
+    }
+
+    /// Pauses or resumes trading on a bonding curve (creator or authority).
+    pub fn set_paused(
+        ctx: Context<SetPaused>,
+        paused: bool,
+    ) -> Result<()> {
+        instructions::set_paused(ctx, paused)
+    }
+
+    /// Moves the sale windows while the curve has no purchases (creator or admin).
+    pub fn update_schedule(
+        ctx: Context<UpdateSchedule>,
+        token_investing_deadline: u64,
+        investing_start_at: u64,
+        whitelist_start_at: u64,
+    ) -> Result<()> {
+        instructions::update_schedule(ctx, token_investing_deadline, investing_start_at, whitelist_start_at)
+    }
+
+    /// Cancels a launch before its sale starts and returns the rent to the creator.
+    pub fn cancel_launch(
+        ctx: Context<CancelLaunch>,
+    ) -> Result<()> {
+        instructions::cancel_launch(ctx)
+    }
+
+    /// Releases the LP of a launch to its beneficiary once the lock expires.
+    pub fn unlock_lp(
+        ctx: Context<UnlockLp>,
+    ) -> Result<()> {
+        instructions::unlock_lp(ctx)
+    }
+
+    /// Returns the LP amount still held by the launch locker.
+    pub fn locked_lp(
+        ctx: Context<LockedLp>,
+    ) -> Result<u64> {
+        instructions::locked_lp(ctx)
+    }
+
+    /// Sets the creator and buyback shares of collected pool fees for a launch.
+    pub fn set_pool_fee_split(
+        ctx: Context<SetPoolFeeSplit>,
+        creator_bps: u16,
+        buyback_bps: u16,
+    ) -> Result<()> {
+        instructions::set_pool_fee_split(ctx, creator_bps, buyback_bps)
+    }
+
+    /// Withdraws the trading fees earned by locked LP and splits them between creator, platform and buyback.
+    pub fn collect_pool_fees(
+        ctx: Context<CollectPoolFees>,
+        min_buyback_token_out: u64,
+    ) -> Result<()> {
+        instructions::collect_pool_fees(ctx, min_buyback_token_out)
+    }
+
+    /// Sets how long the migrated pool stays closed to trading (creator or admin).
+    pub fn set_pool_open_delay(
+        ctx: Context<SetPoolOpenDelay>,
+        pool_open_delay: u64,
+    ) -> Result<()> {
+        instructions::set_pool_open_delay(ctx, pool_open_delay)
+    }
 }
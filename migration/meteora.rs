@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::invoke,
+};
+use raydium_amm_v3::libraries::{liquidity_math, tick_math};
+
+use crate::{errors::Errors, instructions::MigrateLiquidityMeteora, migration::Settlement, utils::transfer_fee_for};
+
+/// Meteora DAMM v2 program, called without a CPI crate
+pub const METEORA_DAMM_V2_PROGRAM_ID: Pubkey = anchor_lang::solana_program::pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
+
+/// Anchor discriminator of `initialize_pool`
+const INITIALIZE_POOL_DISCRIMINATOR: [u8; 8] = [95, 180, 10, 172, 84, 174, 232, 40];
+
+#[derive(AnchorSerialize)]
+struct InitializePoolParameters {
+    liquidity: u128,
+    sqrt_price: u128,
+    activation_point: Option<u64>,
+}
+
+/// Create the full-range pool at the launching price with the caller balances, its position NFT goes to lp_recipient
+pub fn create_pool(accounts: &MigrateLiquidityMeteora, settlement: &Settlement) -> Result<()> {
+    let sqrt_price = settlement.sqrt_price_x64()?;
+    // The launched token is charged its transfer fee again when deposited into the pool
+    let pool_fee = transfer_fee_for(&accounts.mint.to_account_info(), settlement.pool_token_amount)?;
+    let deposit_token_amount = settlement.pool_token_amount - pool_fee;
+    let (amount_a, amount_b) = if settlement.quote_is_token_0 {
+        (settlement.sol_amount, deposit_token_amount)
+    } else {
+        (deposit_token_amount, settlement.sol_amount)
+    };
+
+    // DAMM v2 spans the same sqrt price bounds as the clmm tick math and keeps liquidity in Q64.64
+    let liquidity = liquidity_math::get_liquidity_from_amounts(
+        sqrt_price,
+        tick_math::MIN_SQRT_PRICE_X64,
+        tick_math::MAX_SQRT_PRICE_X64,
+        amount_a,
+        amount_b,
+    );
+    require!(liquidity > 0, Errors::InvalidPrice);
+    let liquidity = liquidity.checked_mul(1u128 << 64).ok_or(Errors::MathOverflow)?;
+
+    let mut data = INITIALIZE_POOL_DISCRIMINATOR.to_vec();
+    InitializePoolParameters {
+        liquidity,
+        sqrt_price,
+        activation_point: None,
+    }
+    .serialize(&mut data)?;
+
+    let ix = Instruction {
+        program_id: METEORA_DAMM_V2_PROGRAM_ID,
+        accounts: vec![
+            AccountMeta::new_readonly(accounts.lp_recipient.key(), false),
+            AccountMeta::new(accounts.position_nft_mint.key(), true),
+            AccountMeta::new(accounts.position_nft_account.key(), false),
+            AccountMeta::new(accounts.creator.key(), true),
+            AccountMeta::new_readonly(accounts.pool_config.key(), false),
+            AccountMeta::new_readonly(accounts.pool_authority.key(), false),
+            AccountMeta::new(accounts.pool.key(), false),
+            AccountMeta::new(accounts.position.key(), false),
+            AccountMeta::new_readonly(accounts.token_0_mint.key(), false),
+            AccountMeta::new_readonly(accounts.token_1_mint.key(), false),
+            AccountMeta::new(accounts.token_0_vault.key(), false),
+            AccountMeta::new(accounts.token_1_vault.key(), false),
+            AccountMeta::new(accounts.creator_token_0.key(), false),
+            AccountMeta::new(accounts.creator_token_1.key(), false),
+            AccountMeta::new_readonly(accounts.token_0_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_1_program.key(), false),
+            AccountMeta::new_readonly(accounts.token_program_2022.key(), false),
+            AccountMeta::new_readonly(accounts.system_program.key(), false),
+            AccountMeta::new_readonly(accounts.event_authority.key(), false),
+            AccountMeta::new_readonly(accounts.meteora_program.key(), false),
+        ],
+        data,
+    };
+
+    invoke(
+        &ix,
+        &[
+            accounts.lp_recipient.to_account_info(),
+            accounts.position_nft_mint.to_account_info(),
+            accounts.position_nft_account.to_account_info(),
+            accounts.creator.to_account_info(),
+            accounts.pool_config.to_account_info(),
+            accounts.pool_authority.to_account_info(),
+            accounts.pool.to_account_info(),
+            accounts.position.to_account_info(),
+            accounts.token_0_mint.to_account_info(),
+            accounts.token_1_mint.to_account_info(),
+            accounts.token_0_vault.to_account_info(),
+            accounts.token_1_vault.to_account_info(),
+            accounts.creator_token_0.to_account_info(),
+            accounts.creator_token_1.to_account_info(),
+            accounts.token_0_program.to_account_info(),
+            accounts.token_1_program.to_account_info(),
+            accounts.token_program_2022.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.event_authority.to_account_info(),
+            accounts.meteora_program.to_account_info(),
+        ],
+    )?;
+
+    Ok(())
+}
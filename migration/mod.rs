@@ -0,0 +1,10 @@
+pub mod settlement;
+pub mod raydium_cp;
+pub mod raydium_clmm;
+pub mod orca_whirlpool;
+pub mod meteora;
+
+pub use settlement::*;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,147 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::{self, Create};
+use anchor_spl::token::{self, Transfer};
+use raydium_amm_v3::libraries::{liquidity_math, tick_math};
+use whirlpool_cpi::{
+    cpi,
+    state::{OpenPositionBumps, WhirlpoolBumps},
+};
+
+use crate::{errors::Errors, instructions::MigrateLiquidityWhirlpool, migration::Settlement};
+
+/// Ticks held by one whirlpool tick array
+pub const TICK_ARRAY_SIZE: i32 = 88;
+
+/// Start tick of the tick array holding `tick_index`
+pub fn tick_array_start_index(tick_index: i32, tick_spacing: u16) -> i32 {
+    let ticks_in_array = TICK_ARRAY_SIZE * tick_spacing as i32;
+    tick_index.div_euclid(ticks_in_array) * ticks_in_array
+}
+
+/// Create the whirlpool at the launching price
+pub fn create_pool(
+    accounts: &MigrateLiquidityWhirlpool,
+    whirlpool_bump: u8,
+    tick_spacing: u16,
+    sqrt_price_x64: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    let cpi_accounts = cpi::accounts::InitializePool {
+        whirlpools_config: accounts.whirlpools_config.to_account_info(),
+        token_mint_a: accounts.token_0_mint.to_account_info(),
+        token_mint_b: accounts.token_1_mint.to_account_info(),
+        funder: accounts.creator.to_account_info(),
+        whirlpool: accounts.whirlpool.to_account_info(),
+        token_vault_a: accounts.token_0_vault.to_account_info(),
+        token_vault_b: accounts.token_1_vault.to_account_info(),
+        fee_tier: accounts.fee_tier.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        rent: accounts.rent.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(accounts.whirlpool_program.to_account_info(), cpi_accounts);
+    cpi::initialize_pool(cpi_context, WhirlpoolBumps { whirlpool_bump }, tick_spacing, sqrt_price_x64)?;
+
+    // A fresh pool has no tick arrays, create the ones the position spans
+    let lower_start_index = tick_array_start_index(tick_lower_index, tick_spacing);
+    let upper_start_index = tick_array_start_index(tick_upper_index, tick_spacing);
+    let mut tick_arrays = vec![(&accounts.tick_array_lower, lower_start_index)];
+    if upper_start_index != lower_start_index {
+        tick_arrays.push((&accounts.tick_array_upper, upper_start_index));
+    }
+    for (tick_array, start_tick_index) in tick_arrays {
+        let cpi_accounts = cpi::accounts::InitializeTickArray {
+            whirlpool: accounts.whirlpool.to_account_info(),
+            funder: accounts.creator.to_account_info(),
+            tick_array: tick_array.to_account_info(),
+            system_program: accounts.system_program.to_account_info(),
+        };
+        let cpi_context = CpiContext::new(accounts.whirlpool_program.to_account_info(), cpi_accounts);
+        cpi::initialize_tick_array(cpi_context, start_tick_index)?;
+    }
+
+    Ok(())
+}
+
+/// Open a position over the tick range from the caller balances and hand its NFT to lp_recipient
+pub fn open_position(
+    accounts: &MigrateLiquidityWhirlpool,
+    settlement: &Settlement,
+    position_bump: u8,
+    sqrt_price_x64: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    // The caller owns the position while funding it, lp_recipient cannot sign here
+    let cpi_accounts = cpi::accounts::OpenPosition {
+        funder: accounts.creator.to_account_info(),
+        owner: accounts.creator.to_account_info(),
+        position: accounts.position.to_account_info(),
+        position_mint: accounts.position_mint.to_account_info(),
+        position_token_account: accounts.position_token_account.to_account_info(),
+        whirlpool: accounts.whirlpool.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        rent: accounts.rent.to_account_info(),
+        associated_token_program: accounts.associated_token_program.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(accounts.whirlpool_program.to_account_info(), cpi_accounts);
+    cpi::open_position(cpi_context, OpenPositionBumps { position_bump }, tick_lower_index, tick_upper_index)?;
+
+    // The largest liquidity both amounts cover in the range, so neither max is exceeded
+    let (amount_0, amount_1) = settlement.pool_amounts();
+    let liquidity = liquidity_math::get_liquidity_from_amounts(
+        sqrt_price_x64,
+        tick_math::get_sqrt_price_at_tick(tick_lower_index)?,
+        tick_math::get_sqrt_price_at_tick(tick_upper_index)?,
+        amount_0,
+        amount_1,
+    );
+    require!(liquidity > 0, Errors::InvalidPrice);
+
+    let cpi_accounts = cpi::accounts::ModifyLiquidity {
+        whirlpool: accounts.whirlpool.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        position_authority: accounts.creator.to_account_info(),
+        position: accounts.position.to_account_info(),
+        position_token_account: accounts.position_token_account.to_account_info(),
+        token_owner_account_a: accounts.creator_token_0.to_account_info(),
+        token_owner_account_b: accounts.creator_token_1.to_account_info(),
+        token_vault_a: accounts.token_0_vault.to_account_info(),
+        token_vault_b: accounts.token_1_vault.to_account_info(),
+        tick_array_lower: accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: accounts.tick_array_upper.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(accounts.whirlpool_program.to_account_info(), cpi_accounts);
+    cpi::increase_liquidity(cpi_context, liquidity, amount_0, amount_1)?;
+
+    // Create the lp_recipient position token account if not exists
+    if accounts.lp_recipient_position_token.data_is_empty() {
+        associated_token::create(
+            CpiContext::new(
+                accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: accounts.creator.to_account_info(),
+                    associated_token: accounts.lp_recipient_position_token.to_account_info(),
+                    authority: accounts.lp_recipient.to_account_info(),
+                    mint: accounts.position_mint.to_account_info(),
+                    system_program: accounts.system_program.to_account_info(),
+                    token_program: accounts.token_program.to_account_info(),
+                },
+            ),
+        )?;
+    }
+
+    token::transfer(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.position_token_account.to_account_info(),
+                to: accounts.lp_recipient_position_token.to_account_info(),
+                authority: accounts.creator.to_account_info(),
+            },
+        ),
+        1,
+    )
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+use raydium_amm_v3::{
+    cpi,
+    libraries::{liquidity_math, tick_math},
+    states::TickArrayState,
+};
+
+use crate::{errors::Errors, instructions::MigrateLiquidityClmm, migration::Settlement, utils::transfer_fee_for};
+
+/// Create the CLMM pool at the launching price
+pub fn create_pool(accounts: &MigrateLiquidityClmm, sqrt_price_x64: u128, open_time: u64) -> Result<()> {
+    let cpi_accounts = cpi::accounts::CreatePool {
+        pool_creator: accounts.creator.to_account_info(),
+        amm_config: accounts.amm_config.to_account_info(),
+        pool_state: accounts.pool_state.to_account_info(),
+        token_mint_0: accounts.token_0_mint.to_account_info(),
+        token_mint_1: accounts.token_1_mint.to_account_info(),
+        token_vault_0: accounts.token_0_vault.to_account_info(),
+        token_vault_1: accounts.token_1_vault.to_account_info(),
+        observation_state: accounts.observation_state.to_account_info(),
+        tick_array_bitmap: accounts.tick_array_bitmap.to_account_info(),
+        token_program_0: accounts.token_0_program.to_account_info(),
+        token_program_1: accounts.token_1_program.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        rent: accounts.rent.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(accounts.clmm_program.to_account_info(), cpi_accounts);
+    cpi::create_pool(cpi_context, sqrt_price_x64, open_time)
+}
+
+/// Open a position over the tick range from the caller balances, its NFT goes straight to lp_recipient
+pub fn open_position(
+    accounts: &MigrateLiquidityClmm,
+    settlement: &Settlement,
+    sqrt_price_x64: u128,
+    tick_lower_index: i32,
+    tick_upper_index: i32,
+) -> Result<()> {
+    // The launched token is charged its transfer fee again when deposited into the pool
+    let pool_fee = transfer_fee_for(&accounts.mint.to_account_info(), settlement.pool_token_amount)?;
+    let deposit_token_amount = settlement.pool_token_amount - pool_fee;
+    let (amount_0_max, amount_1_max) = settlement.pool_amounts();
+    let (deposit_amount_0, deposit_amount_1) = if settlement.quote_is_token_0 {
+        (settlement.sol_amount, deposit_token_amount)
+    } else {
+        (deposit_token_amount, settlement.sol_amount)
+    };
+
+    // The largest liquidity both amounts cover in the range, so neither max is exceeded
+    let liquidity = liquidity_math::get_liquidity_from_amounts(
+        sqrt_price_x64,
+        tick_math::get_sqrt_price_at_tick(tick_lower_index)?,
+        tick_math::get_sqrt_price_at_tick(tick_upper_index)?,
+        deposit_amount_0,
+        deposit_amount_1,
+    );
+    require!(liquidity > 0, Errors::InvalidPrice);
+
+    let tick_spacing = accounts.amm_config.tick_spacing;
+    let cpi_accounts = cpi::accounts::OpenPositionWithToken22Nft {
+        payer: accounts.creator.to_account_info(),
+        position_nft_owner: accounts.lp_recipient.to_account_info(),
+        position_nft_mint: accounts.position_nft_mint.to_account_info(),
+        position_nft_account: accounts.position_nft_account.to_account_info(),
+        pool_state: accounts.pool_state.to_account_info(),
+        protocol_position: accounts.protocol_position.to_account_info(),
+        tick_array_lower: accounts.tick_array_lower.to_account_info(),
+        tick_array_upper: accounts.tick_array_upper.to_account_info(),
+        personal_position: accounts.personal_position.to_account_info(),
+        token_account_0: accounts.creator_token_0.to_account_info(),
+        token_account_1: accounts.creator_token_1.to_account_info(),
+        token_vault_0: accounts.token_0_vault.to_account_info(),
+        token_vault_1: accounts.token_1_vault.to_account_info(),
+        rent: accounts.rent.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        associated_token_program: accounts.associated_token_program.to_account_info(),
+        token_program_2022: accounts.token_program_2022.to_account_info(),
+        vault_0_mint: accounts.token_0_mint.to_account_info(),
+        vault_1_mint: accounts.token_1_mint.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(accounts.clmm_program.to_account_info(), cpi_accounts);
+    cpi::open_position_with_token22_nft(
+        cpi_context,
+        tick_lower_index,
+        tick_upper_index,
+        TickArrayState::get_array_start_index(tick_lower_index, tick_spacing),
+        TickArrayState::get_array_start_index(tick_upper_index, tick_spacing),
+        liquidity,
+        amount_0_max,
+        amount_1_max,
+        true,
+        None,
+    )
+}
@@ -0,0 +1,206 @@
+use anchor_lang::{prelude::*, Discriminator};
+use anchor_spl::associated_token::{self, Create};
+use anchor_spl::token::{self, Burn, Mint, TokenAccount, Transfer};
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+use raydium_cp_swap::{cpi, states::PoolState};
+
+use crate::{errors::Errors, instructions::MigrateLiquidity, migration::Settlement, utils::{isqrt, transfer_fee_for}};
+
+/// Liquidity added to a pool that already existed
+pub struct PoolDeposit {
+    /// sqrt(k) of the deposit and the LP minted for it, pricing each LP for fee collection
+    pub sqrt_k: u128,
+    pub lp_amount: u64,
+    /// Open time the pool was created with, which the migration cannot change
+    pub open_time: u64,
+}
+
+/// Create the CP pool from the caller balances, minting LP to the caller
+pub fn create_pool(accounts: &MigrateLiquidity, init_amount_0: u64, init_amount_1: u64, open_time: u64) -> Result<()> {
+    let cpi_accounts = cpi::accounts::Initialize {
+        creator: accounts.creator.to_account_info(),
+        amm_config: accounts.amm_config.to_account_info(),
+        authority: accounts.authority.to_account_info(),
+        pool_state: accounts.pool_state.to_account_info(),
+        token_0_mint: accounts.token_0_mint.to_account_info(),
+        token_1_mint: accounts.token_1_mint.to_account_info(),
+        lp_mint: accounts.lp_mint.to_account_info(),
+        creator_token_0: accounts.creator_token_0.to_account_info(),
+        creator_token_1: accounts.creator_token_1.to_account_info(),
+        creator_lp_token: accounts.creator_lp_token.to_account_info(),
+        token_0_vault: accounts.token_0_vault.to_account_info(),
+        token_1_vault: accounts.token_1_vault.to_account_info(),
+        create_pool_fee: accounts.create_pool_fee.to_account_info(),
+        observation_state: accounts.observation_state.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        token_0_program: accounts.token_0_program.to_account_info(),
+        token_1_program: accounts.token_1_program.to_account_info(),
+        associated_token_program: accounts.associated_token_program.to_account_info(),
+        system_program: accounts.system_program.to_account_info(),
+        rent: accounts.rent.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(accounts.cp_swap_program.to_account_info(), cpi_accounts);
+    cpi::initialize(cpi_context, init_amount_0, init_amount_1, open_time)
+}
+
+/// Whether someone created the pool for this pair and config before the migration
+pub fn pool_exists(accounts: &MigrateLiquidity) -> bool {
+    !accounts.pool_state.data_is_empty()
+}
+
+/// Add the caller balances to an existing pool at its current ratio, which must match the launching price
+pub fn deposit(accounts: &MigrateLiquidity, settlement: &Settlement) -> Result<PoolDeposit> {
+    let (max_amount_0, max_amount_1) = settlement.pool_amounts();
+    let open_time = existing_pool_state(accounts)?.open_time;
+
+    // The deposit mints LP to the caller, whose LP account cp-swap does not create here
+    if accounts.creator_lp_token.data_is_empty() {
+        associated_token::create(
+            CpiContext::new(
+                accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: accounts.creator.to_account_info(),
+                    associated_token: accounts.creator_lp_token.to_account_info(),
+                    authority: accounts.creator.to_account_info(),
+                    mint: accounts.lp_mint.to_account_info(),
+                    system_program: accounts.system_program.to_account_info(),
+                    token_program: accounts.token_program.to_account_info(),
+                },
+            ),
+        )?;
+    }
+
+    // Raw vault balances include unclaimed protocol fees and the mint supply leaves out the
+    // locked LP, so this slightly undervalues the deposit and the maximums below always hold
+    let (vault_0_before, vault_1_before) = vault_amounts(accounts)?;
+    let lp_supply = Mint::try_deserialize(&mut accounts.lp_mint.try_borrow_data()?.as_ref())?.supply;
+    require!(vault_0_before > 0 && vault_1_before > 0 && lp_supply > 0, Errors::InvalidPoolDeposit);
+    if settlement.quote_is_token_0 {
+        settlement.require_pool_price(vault_0_before, vault_1_before)?;
+    } else {
+        settlement.require_pool_price(vault_1_before, vault_0_before)?;
+    }
+
+    let net_amount_0 = max_amount_0 - transfer_fee_for(&accounts.token_0_mint.to_account_info(), max_amount_0)?;
+    let net_amount_1 = max_amount_1 - transfer_fee_for(&accounts.token_1_mint.to_account_info(), max_amount_1)?;
+    let lp_for_0 = net_amount_0 as u128 * lp_supply as u128 / vault_0_before as u128;
+    let lp_for_1 = net_amount_1 as u128 * lp_supply as u128 / vault_1_before as u128;
+    let lp_token_amount: u64 = lp_for_0.min(lp_for_1).try_into().map_err(|_| Errors::MathOverflow)?;
+    require!(lp_token_amount > 0, Errors::InvalidPoolDeposit);
+
+    let lp_before = caller_lp_amount(accounts)?;
+
+    let cpi_accounts = cpi::accounts::Deposit {
+        owner: accounts.creator.to_account_info(),
+        authority: accounts.authority.to_account_info(),
+        pool_state: accounts.pool_state.to_account_info(),
+        owner_lp_token: accounts.creator_lp_token.to_account_info(),
+        token_0_account: accounts.creator_token_0.to_account_info(),
+        token_1_account: accounts.creator_token_1.to_account_info(),
+        token_0_vault: accounts.token_0_vault.to_account_info(),
+        token_1_vault: accounts.token_1_vault.to_account_info(),
+        token_program: accounts.token_program.to_account_info(),
+        token_program_2022: accounts.token_program_2022.to_account_info(),
+        vault_0_mint: accounts.token_0_mint.to_account_info(),
+        vault_1_mint: accounts.token_1_mint.to_account_info(),
+        lp_mint: accounts.lp_mint.to_account_info(),
+    };
+    let cpi_context = CpiContext::new(accounts.cp_swap_program.to_account_info(), cpi_accounts);
+    cpi::deposit(cpi_context, lp_token_amount, max_amount_0, max_amount_1)?;
+
+    let (vault_0_after, vault_1_after) = vault_amounts(accounts)?;
+    let lp_amount = caller_lp_amount(accounts)? - lp_before;
+
+    Ok(PoolDeposit {
+        sqrt_k: isqrt((vault_0_after - vault_0_before) as u128 * (vault_1_after - vault_1_before) as u128),
+        lp_amount,
+        open_time,
+    })
+}
+
+/// LP tokens held by the caller, none before its LP account exists
+pub fn caller_lp_amount(accounts: &MigrateLiquidity) -> Result<u64> {
+    if accounts.creator_lp_token.data_is_empty() {
+        return Ok(0);
+    }
+    let user_token_lp_account = TokenAccount::try_deserialize(&mut accounts.creator_lp_token.try_borrow_data()?.as_ref())?;
+    Ok(user_token_lp_account.amount)
+}
+
+/// Move the caller LP tokens into the `owner` ATA, which is the lp_recipient or the launch locker
+pub fn deliver_lp<'info>(accounts: &MigrateLiquidity<'info>, owner: AccountInfo<'info>, owner_token_lp: AccountInfo<'info>, amount: u64) -> Result<()> {
+    let owner_ata = associated_token::get_associated_token_address(
+        &owner.key(),
+        &accounts.lp_mint.key()
+    );
+    require!(owner_ata == owner_token_lp.key(), Errors::InvalidLpRecipient);
+
+    // Create the associated token account if not exists
+    if owner_token_lp.data_is_empty() {
+        associated_token::create(
+            CpiContext::new(
+                accounts.associated_token_program.to_account_info(),
+                Create {
+                    payer: accounts.creator.to_account_info(),
+                    associated_token: owner_token_lp.clone(),
+                    authority: owner,
+                    mint: accounts.lp_mint.to_account_info(),
+                    system_program: accounts.system_program.to_account_info(),
+                    token_program: accounts.token_program.to_account_info(),
+                },
+            ),
+        )?;
+    }
+
+    // Transfer liquidity tokens
+    token::transfer(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Transfer {
+                from: accounts.creator_lp_token.to_account_info(),
+                to: owner_token_lp,
+                authority: accounts.creator.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// Burn the caller LP tokens, removing the liquidity from anyone's reach
+pub fn burn_lp(accounts: &MigrateLiquidity, amount: u64) -> Result<()> {
+    token::burn(
+        CpiContext::new(
+            accounts.token_program.to_account_info(),
+            Burn {
+                mint: accounts.lp_mint.to_account_info(),
+                from: accounts.creator_lp_token.to_account_info(),
+                authority: accounts.creator.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
+
+/// sqrt(k) of the vault balances, which CP-swap mints as the initial LP supply
+pub fn pool_sqrt_k(accounts: &MigrateLiquidity) -> Result<u128> {
+    let (amount_0, amount_1) = vault_amounts(accounts)?;
+
+    Ok(isqrt(amount_0 as u128 * amount_1 as u128))
+}
+
+/// State of the existing pool, which was not created yet when the accounts were validated
+fn existing_pool_state(accounts: &MigrateLiquidity) -> Result<PoolState> {
+    require!(*accounts.pool_state.owner == raydium_cp_swap::ID, Errors::InvalidPoolDeposit);
+    let data = accounts.pool_state.try_borrow_data()?;
+    require!(data.starts_with(&PoolState::DISCRIMINATOR), Errors::InvalidPoolDeposit);
+    let pool_data = data.get(8..8 + std::mem::size_of::<PoolState>()).ok_or(Errors::InvalidPoolDeposit)?;
+
+    bytemuck::try_pod_read_unaligned(pool_data).map_err(|_| error!(Errors::InvalidPoolDeposit))
+}
+
+fn vault_amounts(accounts: &MigrateLiquidity) -> Result<(u64, u64)> {
+    let token_0_vault = TokenAccountInterface::try_deserialize(&mut accounts.token_0_vault.try_borrow_data()?.as_ref())?;
+    let token_1_vault = TokenAccountInterface::try_deserialize(&mut accounts.token_1_vault.try_borrow_data()?.as_ref())?;
+
+    Ok((token_0_vault.amount, token_1_vault.amount))
+}
@@ -0,0 +1,365 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, TokenAccount};
+use anchor_spl::token_interface::{self, TokenAccount as TokenAccountInterface, TransferChecked};
+
+use crate::{
+    constants::{BONDING_CURVE_SEED, BONDING_CURVE_VAULT_SEED},
+    errors::Errors,
+    state::{BondingCurve, Global, MigrationTarget},
+    utils::{pay_quote_from_vault, price_deviation_bps, sqrt_price_x64, token_balance, transfer_fee_for, QuoteAccounts},
+};
+
+/// Accounts every migration target settles the curve through
+pub struct SettlementAccounts<'info> {
+    pub caller: Pubkey,
+    pub mint: AccountInfo<'info>,
+    pub mint_decimals: u8,
+    pub quote_mint: AccountInfo<'info>,
+    pub quote_decimals: u8,
+    pub bonding_curve: AccountInfo<'info>,
+    pub bonding_curve_vault: AccountInfo<'info>,
+    pub associated_bonding_curve: AccountInfo<'info>,
+    /// Only passed for launches not raising SOL
+    pub quote_vault: Option<AccountInfo<'info>>,
+    pub token_0_mint: Pubkey,
+    pub token_1_mint: Pubkey,
+    /// Caller token accounts the pool is seeded from
+    pub caller_token_0: AccountInfo<'info>,
+    pub caller_token_1: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub mint_token_program: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub bonding_curve_bump: u8,
+    pub vault_bump: u8,
+}
+
+/// Amounts moved out of the curve into the caller accounts
+pub struct Settlement {
+    /// Tokens leaving the curve
+    pub token_amount: u64,
+    /// Quote leaving the curve vault
+    pub sol_amount: u64,
+    /// Tokens received by the caller, less any Token-2022 transfer fee
+    pub pool_token_amount: u64,
+    /// The quote mint sorts first in the pool
+    pub quote_is_token_0: bool,
+    pub token_launching_price: u64,
+    pub mint_decimals: u8,
+    /// Time the pool opens for trading
+    pub pool_open_time: u64,
+    /// Allowed deviation of the pool price from the launching price
+    pub price_tolerance_bps: u64,
+}
+
+impl Settlement {
+    /// Pool deposit amounts in token 0, token 1 order
+    pub fn pool_amounts(&self) -> (u64, u64) {
+        if self.quote_is_token_0 {
+            (self.sol_amount, self.pool_token_amount)
+        } else {
+            (self.pool_token_amount, self.sol_amount)
+        }
+    }
+
+    /// Q64.64 square root of the launching price of token 0 in token 1
+    pub fn sqrt_price_x64(&self) -> Result<u128> {
+        let token_unit = 10u64.pow(self.mint_decimals.into());
+        if self.quote_is_token_0 {
+            sqrt_price_x64(token_unit, self.token_launching_price)
+        } else {
+            sqrt_price_x64(self.token_launching_price, token_unit)
+        }
+    }
+
+    /// Require a pool holding `quote_amount` against `token_amount` to price the token at launch
+    pub fn require_pool_price(&self, quote_amount: u64, token_amount: u64) -> Result<()> {
+        let deviation = price_deviation_bps(quote_amount, token_amount, self.token_launching_price, self.mint_decimals)?;
+        require!(deviation <= self.price_tolerance_bps, Errors::MigrationPriceMismatch);
+        Ok(())
+    }
+}
+
+/// Check the launch is ready to migrate to `target` and move its quote and tokens to the caller
+pub fn settle<'info>(
+    accounts: &SettlementAccounts<'info>,
+    global: &Global,
+    bonding_curve: &BondingCurve,
+    target: MigrationTarget,
+) -> Result<Settlement> {
+    require!(accounts.caller == global.migration_caller, Errors::NotAuthorized);
+    require!(bonding_curve.completed == true, Errors::BondingCurveNotComplete);
+    require!(bonding_curve.withdrawed == true, Errors::BondingCurveNotWithdrawed);
+    // A fallback migration leaves its reserves escrowed in the curve until a pool takes them
+    require!(bonding_curve.migrated == false || bonding_curve.no_pool == true, Errors::BondingCurveAlreadyMigrated);
+    require!(bonding_curve.migration_target == target, Errors::InvalidMigrationTarget);
+
+    let now: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+    let pool_open_time = now.checked_add(bonding_curve.pool_open_delay).ok_or(Errors::MathOverflow)?;
+
+    let token_amount = bonding_curve.token_reserves;
+    let sol_amount = bonding_curve.sol_reserves;
+
+    // The vaults may hold donations or unclaimed purchases on top, but never less than the reserves
+    if bonding_curve.is_sol_quote() {
+        require!(accounts.bonding_curve_vault.lamports() >= sol_amount, Errors::VaultBalanceMismatch);
+    } else {
+        let quote_vault = accounts.quote_vault.as_ref().ok_or(Errors::MissingAccount)?;
+        let quote_vault = TokenAccount::try_deserialize(&mut quote_vault.try_borrow_data()?.as_ref())?;
+        require!(quote_vault.amount >= sol_amount, Errors::VaultBalanceMismatch);
+    }
+    let associated_bonding_curve = TokenAccountInterface::try_deserialize(&mut accounts.associated_bonding_curve.try_borrow_data()?.as_ref())?;
+    require!(associated_bonding_curve.amount >= token_amount, Errors::TokenBalanceMismatch);
+
+    // A Token-2022 transfer fee is withheld when moving tokens to the caller, only the rest can seed the pool
+    let token_fee = transfer_fee_for(&accounts.mint, token_amount)?;
+    let pool_token_amount = token_amount - token_fee;
+
+    // The withheld transfer fee raises the pool price by up to its own rate
+    let price_tolerance_bps = global.price_tolerance_bps() as u64 + bonding_curve.transfer_fee_bps as u64;
+    let price_deviation = price_deviation_bps(sol_amount, pool_token_amount, bonding_curve.token_launching_price, accounts.mint_decimals)?;
+    require!(price_deviation <= price_tolerance_bps, Errors::MigrationPriceMismatch);
+
+    // The pool pairs exactly the launched mint with the quote mint
+    let quote_is_token_0 = accounts.token_0_mint == accounts.quote_mint.key();
+    let (quote_side, token_side, caller_quote_account, caller_token_account) = if quote_is_token_0 {
+        (accounts.token_0_mint, accounts.token_1_mint, &accounts.caller_token_0, &accounts.caller_token_1)
+    } else {
+        (accounts.token_1_mint, accounts.token_0_mint, &accounts.caller_token_1, &accounts.caller_token_0)
+    };
+    require!(quote_side == accounts.quote_mint.key() && token_side == accounts.mint.key(), Errors::InvalidPoolMints);
+
+    // transfer sol or quote tokens from bonding_curve_vault to caller
+    let mint_key = accounts.mint.key();
+    let vault_seeds = &[
+        BONDING_CURVE_VAULT_SEED.as_bytes(),
+        mint_key.as_ref(),
+        &[accounts.vault_bump],
+    ];
+    let vault_signer_seeds = &[&vault_seeds[..]];
+    let quote = if bonding_curve.is_sol_quote() {
+        None
+    } else {
+        Some(QuoteAccounts {
+            mint: accounts.quote_mint.clone(),
+            vault: accounts.quote_vault.clone().ok_or(Errors::MissingAccount)?,
+            token_program: accounts.token_program.clone(),
+            decimals: accounts.quote_decimals,
+        })
+    };
+    pay_quote_from_vault(
+        &accounts.system_program,
+        &accounts.bonding_curve_vault,
+        quote.as_ref(),
+        caller_quote_account.clone(),
+        sol_amount,
+        vault_signer_seeds,
+    )?;
+    if quote.is_none() {
+        // wrap sol to wsol
+        token::sync_native(CpiContext::new(
+            accounts.token_program.clone(),
+            token::SyncNative {
+                account: caller_quote_account.clone(),
+            },
+        ))?;
+    }
+
+    // Transfer token from bonding_curve to caller
+    let seeds = &[
+        BONDING_CURVE_SEED.as_bytes(),
+        mint_key.as_ref(),
+        &[accounts.bonding_curve_bump],
+    ];
+    let signer_seeds = &[&seeds[..]];
+
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(
+            accounts.mint_token_program.clone(),
+            TransferChecked {
+                from: accounts.associated_bonding_curve.clone(),
+                mint: accounts.mint.clone(),
+                to: caller_token_account.clone(),
+                authority: accounts.bonding_curve.clone(),
+            },
+            signer_seeds,
+        ),
+        token_amount,
+        accounts.mint_decimals,
+    )?;
+
+    Ok(Settlement {
+        token_amount,
+        sol_amount,
+        pool_token_amount,
+        quote_is_token_0,
+        token_launching_price: bonding_curve.token_launching_price,
+        mint_decimals: accounts.mint_decimals,
+        pool_open_time,
+        price_tolerance_bps,
+    })
+}
+
+/// Mark the curve as drained into `pool` and record where the pool lives
+pub fn finish(bonding_curve: &mut BondingCurve, settlement: &Settlement, pool: Pubkey, lp_mint: Pubkey, amm_config: Pubkey) {
+    bonding_curve.token_reserves = 0;
+    bonding_curve.sol_reserves = 0;
+    bonding_curve.migrated = true;
+    bonding_curve.no_pool = false;
+    bonding_curve.pool_open_time = settlement.pool_open_time;
+    bonding_curve.pool = pool;
+    bonding_curve.pool_lp_mint = lp_mint;
+    bonding_curve.pool_amm_config = amm_config;
+}
+
+/// Accounts paying what a pool position leaves in the caller accounts to the leftover recipient
+pub struct LeftoverAccounts<'info> {
+    pub caller: AccountInfo<'info>,
+    pub caller_token_0: AccountInfo<'info>,
+    pub caller_token_1: AccountInfo<'info>,
+    pub token_0_mint: AccountInfo<'info>,
+    pub token_1_mint: AccountInfo<'info>,
+    pub token_0_decimals: u8,
+    pub token_1_decimals: u8,
+    /// Leftover recipient accounts, only needed for a side the pool leaves over
+    pub leftover_token_0: Option<AccountInfo<'info>>,
+    pub leftover_token_1: Option<AccountInfo<'info>>,
+    pub token_0_program: AccountInfo<'info>,
+    pub token_1_program: AccountInfo<'info>,
+}
+
+impl<'info> LeftoverAccounts<'info> {
+    /// Caller balances read from the account data, which CPIs have changed since deserialization
+    pub fn caller_amounts(&self) -> Result<(u64, u64)> {
+        Ok((token_balance(&self.caller_token_0)?, token_balance(&self.caller_token_1)?))
+    }
+}
+
+/// Implement `settlement_accounts` and `leftover_accounts` for a migrate instruction from the fields every target
+/// names alike, `$token_0_program` and `$token_1_program` being the programs of each pool side
+macro_rules! migration_accounts {
+    ($accounts:ident, $bumps:ident, $token_0_program:ident, $token_1_program:ident) => {
+        impl<'info> $accounts<'info> {
+            fn settlement_accounts(&self, bumps: &$bumps) -> $crate::migration::SettlementAccounts<'info> {
+                $crate::migration::SettlementAccounts {
+                    caller: self.creator.key(),
+                    mint: self.mint.to_account_info(),
+                    mint_decimals: self.mint.decimals,
+                    quote_mint: self.quote_mint.to_account_info(),
+                    quote_decimals: self.quote_mint.decimals,
+                    bonding_curve: self.bonding_curve.to_account_info(),
+                    bonding_curve_vault: self.bonding_curve_vault.to_account_info(),
+                    associated_bonding_curve: self.associated_bonding_curve.to_account_info(),
+                    quote_vault: self.quote_vault.as_ref().map(|quote_vault| quote_vault.to_account_info()),
+                    token_0_mint: self.token_0_mint.key(),
+                    token_1_mint: self.token_1_mint.key(),
+                    caller_token_0: self.creator_token_0.to_account_info(),
+                    caller_token_1: self.creator_token_1.to_account_info(),
+                    token_program: self.token_program.to_account_info(),
+                    mint_token_program: self.mint_token_program.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                    bonding_curve_bump: bumps.bonding_curve,
+                    vault_bump: bumps.bonding_curve_vault,
+                }
+            }
+
+            fn leftover_accounts(&self) -> $crate::migration::LeftoverAccounts<'info> {
+                $crate::migration::LeftoverAccounts {
+                    caller: self.creator.to_account_info(),
+                    caller_token_0: self.creator_token_0.to_account_info(),
+                    caller_token_1: self.creator_token_1.to_account_info(),
+                    token_0_mint: self.token_0_mint.to_account_info(),
+                    token_1_mint: self.token_1_mint.to_account_info(),
+                    token_0_decimals: self.token_0_mint.decimals,
+                    token_1_decimals: self.token_1_mint.decimals,
+                    leftover_token_0: self.leftover_token_0.as_ref().map(|leftover_token_0| leftover_token_0.to_account_info()),
+                    leftover_token_1: self.leftover_token_1.as_ref().map(|leftover_token_1| leftover_token_1.to_account_info()),
+                    token_0_program: self.$token_0_program.to_account_info(),
+                    token_1_program: self.$token_1_program.to_account_info(),
+                }
+            }
+        }
+    };
+}
+pub(crate) use migration_accounts;
+
+/// Pay what the pool did not take of the settled amounts, given the caller balances before the deposit
+pub fn send_leftovers(accounts: &LeftoverAccounts, settlement: &Settlement, caller_before: (u64, u64)) -> Result<(u64, u64)> {
+    let (init_amount_0, init_amount_1) = settlement.pool_amounts();
+    let (caller_0_after, caller_1_after) = accounts.caller_amounts()?;
+    let leftover_0 = init_amount_0.saturating_sub(caller_before.0.checked_sub(caller_0_after).ok_or(Errors::MathOverflow)?);
+    let leftover_1 = init_amount_1.saturating_sub(caller_before.1.checked_sub(caller_1_after).ok_or(Errors::MathOverflow)?);
+
+    send_leftover(accounts, &accounts.caller_token_0, &accounts.token_0_mint, accounts.token_0_decimals, &accounts.leftover_token_0, &accounts.token_0_program, leftover_0)?;
+    send_leftover(accounts, &accounts.caller_token_1, &accounts.token_1_mint, accounts.token_1_decimals, &accounts.leftover_token_1, &accounts.token_1_program, leftover_1)?;
+
+    Ok((leftover_0, leftover_1))
+}
+
+fn send_leftover<'info>(
+    accounts: &LeftoverAccounts<'info>,
+    caller_token: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    decimals: u8,
+    leftover_token: &Option<AccountInfo<'info>>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    let leftover_token = leftover_token.as_ref().ok_or(Errors::MissingAccount)?;
+    token_interface::transfer_checked(
+        CpiContext::new(
+            token_program.clone(),
+            TransferChecked {
+                from: caller_token.clone(),
+                mint: mint.clone(),
+                to: leftover_token.clone(),
+                authority: accounts.caller.clone(),
+            },
+        ),
+        amount,
+        decimals,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn settlement(quote_is_token_0: bool) -> Settlement {
+        Settlement {
+            token_amount: 2_000_000,
+            sol_amount: 1_000,
+            pool_token_amount: 2_000_000,
+            quote_is_token_0,
+            token_launching_price: 500,
+            mint_decimals: 6,
+            pool_open_time: 0,
+            price_tolerance_bps: 100,
+        }
+    }
+
+    #[test]
+    fn pool_amounts_follow_mint_order() {
+        assert_eq!(settlement(true).pool_amounts(), (1_000, 2_000_000));
+        assert_eq!(settlement(false).pool_amounts(), (2_000_000, 1_000));
+    }
+
+    #[test]
+    fn sqrt_price_follows_mint_order() {
+        // 500 quote per 10^6 token units, inverted when the quote sorts first
+        assert_eq!(settlement(false).sqrt_price_x64().unwrap(), sqrt_price_x64(500, 1_000_000).unwrap());
+        assert_eq!(settlement(true).sqrt_price_x64().unwrap(), sqrt_price_x64(1_000_000, 500).unwrap());
+    }
+
+    #[test]
+    fn pool_price_within_tolerance() {
+        let settlement = settlement(false);
+        assert!(settlement.require_pool_price(1_000, 2_000_000).is_ok());
+        assert!(settlement.require_pool_price(1_010, 2_000_000).is_ok());
+        assert!(settlement.require_pool_price(1_011, 2_000_000).is_err());
+        assert!(settlement.require_pool_price(989, 2_000_000).is_err());
+    }
+}
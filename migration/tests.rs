@@ -0,0 +1,797 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        entrypoint::ProgramResult,
+        hash::hash,
+        instruction::{Instruction, InstructionError},
+        program_option::COption,
+        program_pack::Pack,
+        sysvar,
+    },
+    system_program::{self, CreateAccount},
+    Discriminator, InstructionData, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::{self, get_associated_token_address, Create},
+    token::{
+        self,
+        spl_token::{
+            self,
+            native_mint,
+            state::{Account as TokenState, AccountState, Mint as MintState},
+        },
+        InitializeMint2, MintTo, Transfer,
+    },
+};
+use raydium_cp_swap::states::PoolState;
+use solana_program_test::{processor, BanksClient, BanksClientError, ProgramTest};
+use solana_sdk::{
+    account::Account as SolanaAccount,
+    signature::{Keypair, Signer as _},
+    transaction::{Transaction, TransactionError},
+};
+use std::mem::size_of;
+
+use crate::{
+    constants::{BONDING_CURVE_SEED, BONDING_CURVE_VAULT_SEED, GLOBAL_SEED, POOL_REGISTRY_SEED},
+    errors::Errors,
+    migration::meteora::METEORA_DAMM_V2_PROGRAM_ID,
+    state::{BondingCurve, Global, LpDisposition, MigrationTarget},
+    utils::isqrt,
+};
+
+const TOKEN_DECIMALS: u8 = 6;
+const TOKEN_RESERVES: u64 = 200_000_000_000;
+const SOL_RESERVES: u64 = 1_000_000_000;
+/// Lamports per 10^6 token units, pricing the token reserves at the SOL reserves
+const LAUNCHING_PRICE: u64 = 5_000;
+
+/// The launchpad entrypoint, whose accounts must outlive the instruction
+fn launchpad_entry(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    crate::entry(program_id, accounts, data)
+}
+
+/// Stand-in for every venue: pool, tick array and config setup is a no-op, deposits move tokens from the
+/// signer-owned source accounts into the mock vaults
+fn mock_venue(program_id: &Pubkey, accounts: &[AccountInfo], data: &[u8]) -> ProgramResult {
+    let (discriminator, args) = data.split_at(8);
+    let result = if discriminator == sighash("initialize") {
+        // cp-swap opens the pool with exactly the initial amounts and mints sqrt(k) LP
+        let (amount_0, amount_1) = (read_u64(args, 0), read_u64(args, 8));
+        deposit(program_id, accounts, |side, _| if side == 0 { amount_0 } else { amount_1 })
+            .and_then(|(deposited_0, deposited_1)| mint_lp(program_id, accounts, isqrt(deposited_0 as u128 * deposited_1 as u128) as u64))
+    } else if discriminator == sighash("deposit") {
+        // The deposit into an existing cp-swap pool leaves half of token 1 over
+        deposit(program_id, accounts, half_of_token_1).and_then(|_| mint_lp(program_id, accounts, read_u64(args, 0)))
+    } else if discriminator == sighash("open_position_with_token22_nft")
+        || discriminator == sighash("increase_liquidity")
+        || (discriminator == sighash("initialize_pool") && *program_id == METEORA_DAMM_V2_PROGRAM_ID)
+    {
+        // Concentrated positions are bound by one side, the other is left over
+        deposit(program_id, accounts, half_of_token_1).map(|_| ())
+    } else if discriminator == sighash("open_position") {
+        open_whirlpool_position(accounts)
+    } else {
+        Ok(())
+    };
+
+    result.map_err(Into::into)
+}
+
+fn sighash(name: &str) -> [u8; 8] {
+    hash(format!("global:{name}").as_bytes()).to_bytes()[..8].try_into().unwrap()
+}
+
+fn read_u64(args: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(args[offset..offset + 8].try_into().unwrap())
+}
+
+fn half_of_token_1(side: usize, balance: u64) -> u64 {
+    if side == 0 { balance } else { balance / 2 }
+}
+
+/// Owner of the mock pool vaults, never a signer so deposits tell vaults from sources
+fn mock_vault_authority(venue: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"vault_authority"], venue).0
+}
+
+fn token_state(account: &AccountInfo) -> Option<TokenState> {
+    if *account.owner != spl_token::ID || account.data_len() != TokenState::LEN {
+        return None;
+    }
+    TokenState::unpack(&account.data.borrow()).ok()
+}
+
+fn mint_authority(account: &AccountInfo) -> Option<Pubkey> {
+    if *account.owner != spl_token::ID || account.data_len() != MintState::LEN {
+        return None;
+    }
+    MintState::unpack(&account.data.borrow()).ok()?.mint_authority.into()
+}
+
+fn is_signer(accounts: &[AccountInfo], key: &Pubkey) -> bool {
+    accounts.iter().any(|account| account.key == key && account.is_signer)
+}
+
+fn find<'a, 'info>(accounts: &'a [AccountInfo<'info>], predicate: impl Fn(&AccountInfo) -> bool) -> Result<&'a AccountInfo<'info>> {
+    Ok(accounts.iter().find(|account| predicate(account)).ok_or(ProgramError::NotEnoughAccountKeys)?)
+}
+
+/// Move `amount(side, balance)` of each pool side, in mint order, from its source into its vault
+fn deposit<'info>(program_id: &Pubkey, accounts: &[AccountInfo<'info>], amount: impl Fn(usize, u64) -> u64) -> Result<(u64, u64)> {
+    let token_program = find(accounts, |account| *account.key == spl_token::ID)?;
+    let token_accounts: Vec<_> = accounts.iter().filter_map(|account| token_state(account).map(|state| (account, state))).collect();
+    let vault_authority = mock_vault_authority(program_id);
+    let mut vaults: Vec<_> = token_accounts.iter().filter(|(_, state)| state.owner == vault_authority).collect();
+    vaults.sort_by_key(|(_, state)| state.mint);
+    require!(vaults.len() == 2, Errors::MissingAccount);
+
+    let mut deposited = [0; 2];
+    for (side, (vault, vault_state)) in vaults.into_iter().enumerate() {
+        let (source, source_state) = token_accounts
+            .iter()
+            .find(|(_, state)| state.mint == vault_state.mint && is_signer(accounts, &state.owner))
+            .ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let authority = find(accounts, |account| *account.key == source_state.owner)?;
+        deposited[side] = amount(side, source_state.amount);
+        token::transfer(
+            CpiContext::new(
+                token_program.to_account_info(),
+                Transfer {
+                    from: source.to_account_info(),
+                    to: vault.to_account_info(),
+                    authority: authority.to_account_info(),
+                },
+            ),
+            deposited[side],
+        )?;
+    }
+
+    Ok((deposited[0], deposited[1]))
+}
+
+/// Mint `amount` LP to the signer-owned LP account, the cp-swap authority signing
+fn mint_lp<'info>(program_id: &Pubkey, accounts: &[AccountInfo<'info>], amount: u64) -> Result<()> {
+    let (authority, bump) = Pubkey::find_program_address(&[raydium_cp_swap::AUTH_SEED.as_bytes()], program_id);
+    let token_program = find(accounts, |account| *account.key == spl_token::ID)?;
+    let lp_mint = find(accounts, |account| mint_authority(account) == Some(authority))?;
+    let lp_token = find(accounts, |account| {
+        token_state(account).is_some_and(|state| state.mint == *lp_mint.key && is_signer(accounts, &state.owner))
+    })?;
+    let authority = find(accounts, |account| *account.key == authority)?;
+
+    token::mint_to(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            MintTo {
+                mint: lp_mint.to_account_info(),
+                to: lp_token.to_account_info(),
+                authority: authority.to_account_info(),
+            },
+            &[&[raydium_cp_swap::AUTH_SEED.as_bytes(), &[bump]]],
+        ),
+        amount,
+    )
+}
+
+/// Create the position mint and mint its NFT to the owner ATA, the owner acting as the mint authority
+fn open_whirlpool_position(accounts: &[AccountInfo]) -> Result<()> {
+    let owner = find(accounts, |account| account.is_signer && account.lamports() > 0 && account.data_is_empty())?;
+    let position_mint = find(accounts, |account| account.is_signer && account.lamports() == 0)?;
+    let position_token_account = get_associated_token_address(owner.key, position_mint.key);
+    let position_token_account = find(accounts, |account| *account.key == position_token_account)?;
+    let token_program = find(accounts, |account| *account.key == spl_token::ID)?;
+    let system_program = find(accounts, |account| *account.key == system_program::ID)?;
+    let associated_token_program = find(accounts, |account| *account.key == associated_token::ID)?;
+
+    system_program::create_account(
+        CpiContext::new(
+            system_program.to_account_info(),
+            CreateAccount {
+                from: owner.to_account_info(),
+                to: position_mint.to_account_info(),
+            },
+        ),
+        Rent::get()?.minimum_balance(MintState::LEN),
+        MintState::LEN as u64,
+        &spl_token::ID,
+    )?;
+    token::initialize_mint2(
+        CpiContext::new(
+            token_program.to_account_info(),
+            InitializeMint2 {
+                mint: position_mint.to_account_info(),
+            },
+        ),
+        0,
+        owner.key,
+        None,
+    )?;
+    associated_token::create(CpiContext::new(
+        associated_token_program.to_account_info(),
+        Create {
+            payer: owner.to_account_info(),
+            associated_token: position_token_account.to_account_info(),
+            authority: owner.to_account_info(),
+            mint: position_mint.to_account_info(),
+            system_program: system_program.to_account_info(),
+            token_program: token_program.to_account_info(),
+        },
+    ))?;
+    token::mint_to(
+        CpiContext::new(
+            token_program.to_account_info(),
+            MintTo {
+                mint: position_mint.to_account_info(),
+                to: position_token_account.to_account_info(),
+                authority: owner.to_account_info(),
+            },
+        ),
+        1,
+    )
+}
+
+fn add_packed<T: Pack>(program_test: &mut ProgramTest, address: Pubkey, state: T, extra_lamports: u64) {
+    let mut data = vec![0; T::LEN];
+    T::pack(state, &mut data).unwrap();
+    program_test.add_account(
+        address,
+        SolanaAccount {
+            lamports: Rent::default().minimum_balance(T::LEN) + extra_lamports,
+            data,
+            owner: spl_token::ID,
+            ..SolanaAccount::default()
+        },
+    );
+}
+
+fn add_mint(program_test: &mut ProgramTest, address: Pubkey, decimals: u8, supply: u64, mint_authority: Option<Pubkey>) {
+    let mint = MintState {
+        mint_authority: mint_authority.into(),
+        supply,
+        decimals,
+        is_initialized: true,
+        ..MintState::default()
+    };
+    add_packed(program_test, address, mint, 0);
+}
+
+/// Wrapped SOL accounts hold their amount as lamports on top of the rent reserve
+fn add_token_account(program_test: &mut ProgramTest, address: Pubkey, mint: Pubkey, owner: Pubkey, amount: u64) {
+    let is_native = mint == native_mint::ID;
+    let token_account = TokenState {
+        mint,
+        owner,
+        amount,
+        state: AccountState::Initialized,
+        is_native: if is_native { COption::Some(Rent::default().minimum_balance(TokenState::LEN)) } else { COption::None },
+        ..TokenState::default()
+    };
+    add_packed(program_test, address, token_account, if is_native { amount } else { 0 });
+}
+
+fn add_program_account<T: AccountSerialize>(program_test: &mut ProgramTest, address: Pubkey, owner: Pubkey, account: &T, space: usize) {
+    let mut data = Vec::new();
+    account.try_serialize(&mut data).unwrap();
+    data.resize(space.max(data.len()), 0);
+    program_test.add_account(
+        address,
+        SolanaAccount {
+            lamports: Rent::default().minimum_balance(data.len()),
+            data,
+            owner,
+            ..SolanaAccount::default()
+        },
+    );
+}
+
+/// A completed and withdrawn SOL launch, its curve holding reserves priced at launch
+struct Launch {
+    program_test: ProgramTest,
+    creator: Keypair,
+    mint: Pubkey,
+    lp_recipient: Pubkey,
+    leftover_recipient: Pubkey,
+    token_0_mint: Pubkey,
+    token_1_mint: Pubkey,
+}
+
+impl Launch {
+    fn new(target: MigrationTarget) -> Self {
+        let mut program_test = ProgramTest::new("memechef", crate::ID, processor!(launchpad_entry));
+        for venue in [raydium_cp_swap::ID, raydium_amm_v3::ID, whirlpool_cpi::ID, METEORA_DAMM_V2_PROGRAM_ID] {
+            program_test.add_program("mock_venue", venue, processor!(mock_venue));
+        }
+
+        let mint = Pubkey::new_unique();
+        let (token_0_mint, token_1_mint) = if native_mint::ID < mint { (native_mint::ID, mint) } else { (mint, native_mint::ID) };
+        let mut launch = Self {
+            program_test,
+            creator: Keypair::new(),
+            mint,
+            lp_recipient: Pubkey::new_unique(),
+            leftover_recipient: Pubkey::new_unique(),
+            token_0_mint,
+            token_1_mint,
+        };
+        let creator = launch.creator.pubkey();
+        let (global, bonding_curve, bonding_curve_vault) = (launch.global(), launch.bonding_curve(), launch.bonding_curve_vault());
+        let associated_bonding_curve = launch.associated_bonding_curve();
+        let token_accounts: Vec<_> = [token_0_mint, token_1_mint]
+            .into_iter()
+            .flat_map(|token_mint| [
+                (launch.creator_token(token_mint), token_mint, creator),
+                (launch.leftover_token(token_mint), token_mint, launch.leftover_recipient),
+            ])
+            .collect();
+
+        let global_state = Global {
+            initialized: true,
+            fee_recipient: launch.leftover_recipient,
+            lp_recipient: launch.lp_recipient,
+            migration_caller: creator,
+            ..Global::default()
+        };
+        let bonding_curve_state = BondingCurve {
+            sol_reserves: SOL_RESERVES,
+            token_reserves: TOKEN_RESERVES,
+            token_launching_price: LAUNCHING_PRICE,
+            completed: true,
+            withdrawed: true,
+            mint,
+            creator,
+            migration_target: target,
+            lp_disposition: LpDisposition::Recipient,
+            ..BondingCurve::default()
+        };
+
+        let program_test = &mut launch.program_test;
+        program_test.add_account(creator, SolanaAccount { lamports: 10_000_000_000, ..SolanaAccount::default() });
+        add_mint(program_test, mint, TOKEN_DECIMALS, TOKEN_RESERVES, None);
+        add_mint(program_test, native_mint::ID, 9, 0, None);
+        add_program_account(program_test, global, crate::ID, &global_state, size_of::<Global>() + 8);
+        add_program_account(program_test, bonding_curve, crate::ID, &bonding_curve_state, size_of::<BondingCurve>() + 8);
+        program_test.add_account(bonding_curve_vault, SolanaAccount { lamports: SOL_RESERVES, ..SolanaAccount::default() });
+        add_token_account(program_test, associated_bonding_curve, mint, bonding_curve, TOKEN_RESERVES);
+        for (address, token_mint, owner) in token_accounts {
+            add_token_account(program_test, address, token_mint, owner, 0);
+        }
+
+        launch
+    }
+
+    fn global(&self) -> Pubkey {
+        Pubkey::find_program_address(&[GLOBAL_SEED.as_bytes()], &crate::ID).0
+    }
+
+    fn bonding_curve(&self) -> Pubkey {
+        Pubkey::find_program_address(&[BONDING_CURVE_SEED.as_bytes(), self.mint.as_ref()], &crate::ID).0
+    }
+
+    fn bonding_curve_vault(&self) -> Pubkey {
+        Pubkey::find_program_address(&[BONDING_CURVE_VAULT_SEED.as_bytes(), self.mint.as_ref()], &crate::ID).0
+    }
+
+    fn associated_bonding_curve(&self) -> Pubkey {
+        get_associated_token_address(&self.bonding_curve(), &self.mint)
+    }
+
+    fn creator_token(&self, mint: Pubkey) -> Pubkey {
+        get_associated_token_address(&self.creator.pubkey(), &mint)
+    }
+
+    fn leftover_token(&self, mint: Pubkey) -> Pubkey {
+        get_associated_token_address(&self.leftover_recipient, &mint)
+    }
+
+    /// Settled amounts in token 0, token 1 order
+    fn pool_amounts(&self) -> (u64, u64) {
+        if self.token_0_mint == native_mint::ID {
+            (SOL_RESERVES, TOKEN_RESERVES)
+        } else {
+            (TOKEN_RESERVES, SOL_RESERVES)
+        }
+    }
+
+    /// Empty pool vault of `mint` owned by the mock `venue`
+    fn add_vault(&mut self, address: Pubkey, mint: Pubkey, venue: &Pubkey, amount: u64) {
+        add_token_account(&mut self.program_test, address, mint, mock_vault_authority(venue), amount);
+    }
+
+    /// Run `instruction` paid and signed by the creator along with `signers`
+    async fn process(&mut self, instruction: Instruction, signers: &[&Keypair]) -> (BanksClient, std::result::Result<(), BanksClientError>) {
+        let (mut banks_client, _payer, recent_blockhash) = std::mem::take(&mut self.program_test).start().await;
+        let mut all_signers = vec![&self.creator];
+        all_signers.extend_from_slice(signers);
+        let transaction = Transaction::new_signed_with_payer(&[instruction], Some(&self.creator.pubkey()), &all_signers[..], recent_blockhash);
+        let result = banks_client.process_transaction(transaction).await;
+
+        (banks_client, result)
+    }
+}
+
+async fn token_amount(banks_client: &mut BanksClient, address: Pubkey) -> u64 {
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    TokenState::unpack(&account.data).unwrap().amount
+}
+
+async fn bonding_curve(banks_client: &mut BanksClient, launch: &Launch) -> BondingCurve {
+    let account = banks_client.get_account(launch.bonding_curve()).await.unwrap().unwrap();
+    BondingCurve::try_deserialize(&mut account.data.as_ref()).unwrap()
+}
+
+/// The curve is drained and marked migrated into `pool`
+async fn assert_migrated(banks_client: &mut BanksClient, launch: &Launch, pool: Pubkey) {
+    let bonding_curve = bonding_curve(banks_client, launch).await;
+    assert!(bonding_curve.migrated);
+    assert_eq!(bonding_curve.pool, pool);
+    assert_eq!((bonding_curve.token_reserves, bonding_curve.sol_reserves), (0, 0));
+    assert_eq!(banks_client.get_balance(launch.bonding_curve_vault()).await.unwrap(), 0);
+    assert_eq!(token_amount(banks_client, launch.associated_bonding_curve()).await, 0);
+    assert_eq!(token_amount(banks_client, launch.creator_token(launch.token_0_mint)).await, 0);
+    assert_eq!(token_amount(banks_client, launch.creator_token(launch.token_1_mint)).await, 0);
+}
+
+/// The venue took all of token 0 and half of token 1, the rest of token 1 went to the leftover recipient
+async fn assert_leftovers_sent(banks_client: &mut BanksClient, launch: &Launch, vault_0: Pubkey, vault_1: Pubkey, vault_amounts_before: (u64, u64)) {
+    let (amount_0, amount_1) = launch.pool_amounts();
+    assert_eq!(token_amount(banks_client, vault_0).await, vault_amounts_before.0 + amount_0);
+    assert_eq!(token_amount(banks_client, vault_1).await, vault_amounts_before.1 + amount_1 / 2);
+    assert_eq!(token_amount(banks_client, launch.leftover_token(launch.token_0_mint)).await, 0);
+    assert_eq!(token_amount(banks_client, launch.leftover_token(launch.token_1_mint)).await, amount_1 - amount_1 / 2);
+}
+
+/// Raydium CP accounts of the launch, the pool already holding `pool_amounts` when `existing_lp_supply` is set
+fn raydium_cp_accounts(launch: &mut Launch, pool_amounts: (u64, u64), existing_lp_supply: u64) -> crate::accounts::MigrateLiquidity {
+    let cp_swap = raydium_cp_swap::ID;
+    let amm_config = Pubkey::new_unique();
+    let pool_state = Pubkey::find_program_address(
+        &[raydium_cp_swap::states::POOL_SEED.as_bytes(), amm_config.as_ref(), launch.token_0_mint.as_ref(), launch.token_1_mint.as_ref()],
+        &cp_swap,
+    )
+    .0;
+    let authority = Pubkey::find_program_address(&[raydium_cp_swap::AUTH_SEED.as_bytes()], &cp_swap).0;
+    let lp_mint = Pubkey::find_program_address(&[raydium_cp_swap::states::POOL_LP_MINT_SEED.as_bytes(), pool_state.as_ref()], &cp_swap).0;
+    let vault = |mint: &Pubkey| {
+        Pubkey::find_program_address(&[raydium_cp_swap::states::POOL_VAULT_SEED.as_bytes(), pool_state.as_ref(), mint.as_ref()], &cp_swap).0
+    };
+    let (token_0_vault, token_1_vault) = (vault(&launch.token_0_mint), vault(&launch.token_1_mint));
+    let create_pool_fee = raydium_cp_swap::create_pool_fee_reveiver::id();
+    let creator_lp_token = get_associated_token_address(&launch.creator.pubkey(), &lp_mint);
+
+    add_program_account(&mut launch.program_test, amm_config, cp_swap, &raydium_cp_swap::states::AmmConfig::default(), 0);
+    add_mint(&mut launch.program_test, lp_mint, 9, existing_lp_supply, Some(authority));
+    add_token_account(&mut launch.program_test, creator_lp_token, lp_mint, launch.creator.pubkey(), 0);
+    add_token_account(&mut launch.program_test, create_pool_fee, native_mint::ID, Pubkey::new_unique(), 0);
+    let (token_0_mint, token_1_mint) = (launch.token_0_mint, launch.token_1_mint);
+    launch.add_vault(token_0_vault, token_0_mint, &cp_swap, pool_amounts.0);
+    launch.add_vault(token_1_vault, token_1_mint, &cp_swap, pool_amounts.1);
+
+    if existing_lp_supply > 0 {
+        let mut pool: PoolState = bytemuck::Zeroable::zeroed();
+        pool.open_time = 42;
+        let mut data = PoolState::DISCRIMINATOR.to_vec();
+        data.extend_from_slice(bytemuck::bytes_of(&pool));
+        launch.program_test.add_account(
+            pool_state,
+            SolanaAccount {
+                lamports: Rent::default().minimum_balance(data.len()),
+                data,
+                owner: cp_swap,
+                ..SolanaAccount::default()
+            },
+        );
+    }
+
+    crate::accounts::MigrateLiquidity {
+        global: launch.global(),
+        mint: launch.mint,
+        quote_mint: native_mint::ID,
+        bonding_curve: launch.bonding_curve(),
+        bonding_curve_vault: launch.bonding_curve_vault(),
+        associated_bonding_curve: launch.associated_bonding_curve(),
+        quote_vault: None,
+        lp_recipient: launch.lp_recipient,
+        lp_recipient_token_lp: Some(get_associated_token_address(&launch.lp_recipient, &lp_mint)),
+        lp_locker: None,
+        lp_locker_token: None,
+        cp_swap_program: cp_swap,
+        creator: launch.creator.pubkey(),
+        amm_config,
+        authority,
+        pool_state,
+        pool_registry: Pubkey::find_program_address(&[POOL_REGISTRY_SEED.as_bytes(), pool_state.as_ref()], &crate::ID).0,
+        token_0_mint,
+        token_1_mint,
+        lp_mint,
+        creator_token_0: launch.creator_token(token_0_mint),
+        creator_token_1: launch.creator_token(token_1_mint),
+        creator_lp_token,
+        token_0_vault,
+        token_1_vault,
+        leftover_token_0: Some(launch.leftover_token(token_0_mint)),
+        leftover_token_1: Some(launch.leftover_token(token_1_mint)),
+        create_pool_fee,
+        observation_state: Pubkey::find_program_address(&[raydium_cp_swap::states::OBSERVATION_SEED.as_bytes(), pool_state.as_ref()], &cp_swap).0,
+        token_program: spl_token::ID,
+        token_program_2022: anchor_spl::token_2022::ID,
+        mint_token_program: spl_token::ID,
+        token_0_program: spl_token::ID,
+        token_1_program: spl_token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    }
+}
+
+fn meteora_accounts(launch: &mut Launch) -> (crate::accounts::MigrateLiquidityMeteora, Keypair) {
+    let position_nft_mint = Keypair::new();
+    let (token_0_mint, token_1_mint) = (launch.token_0_mint, launch.token_1_mint);
+    let (token_0_vault, token_1_vault) = (Pubkey::new_unique(), Pubkey::new_unique());
+    launch.add_vault(token_0_vault, token_0_mint, &METEORA_DAMM_V2_PROGRAM_ID, 0);
+    launch.add_vault(token_1_vault, token_1_mint, &METEORA_DAMM_V2_PROGRAM_ID, 0);
+
+    let accounts = crate::accounts::MigrateLiquidityMeteora {
+        global: launch.global(),
+        mint: launch.mint,
+        quote_mint: native_mint::ID,
+        bonding_curve: launch.bonding_curve(),
+        bonding_curve_vault: launch.bonding_curve_vault(),
+        associated_bonding_curve: launch.associated_bonding_curve(),
+        quote_vault: None,
+        lp_recipient: launch.lp_recipient,
+        meteora_program: METEORA_DAMM_V2_PROGRAM_ID,
+        creator: launch.creator.pubkey(),
+        pool_config: Pubkey::new_unique(),
+        pool_authority: Pubkey::new_unique(),
+        pool: Pubkey::new_unique(),
+        position: Pubkey::new_unique(),
+        position_nft_mint: position_nft_mint.pubkey(),
+        position_nft_account: Pubkey::new_unique(),
+        token_0_mint,
+        token_1_mint,
+        creator_token_0: launch.creator_token(token_0_mint),
+        creator_token_1: launch.creator_token(token_1_mint),
+        token_0_vault,
+        token_1_vault,
+        leftover_token_0: Some(launch.leftover_token(token_0_mint)),
+        leftover_token_1: Some(launch.leftover_token(token_1_mint)),
+        event_authority: Pubkey::new_unique(),
+        token_program: spl_token::ID,
+        token_program_2022: anchor_spl::token_2022::ID,
+        mint_token_program: spl_token::ID,
+        token_0_program: spl_token::ID,
+        token_1_program: spl_token::ID,
+        system_program: system_program::ID,
+    };
+
+    (accounts, position_nft_mint)
+}
+
+#[tokio::test]
+async fn migrates_into_new_raydium_cp_pool() {
+    let mut launch = Launch::new(MigrationTarget::RaydiumCp);
+    let accounts = raydium_cp_accounts(&mut launch, (0, 0), 0);
+    let (pool_state, lp_recipient_token_lp) = (accounts.pool_state, accounts.lp_recipient_token_lp.unwrap());
+    let (token_0_vault, token_1_vault) = (accounts.token_0_vault, accounts.token_1_vault);
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateLiquidity {}.data(),
+    };
+
+    let (mut banks_client, result) = launch.process(instruction, &[]).await;
+    result.unwrap();
+
+    assert_migrated(&mut banks_client, &launch, pool_state).await;
+    let (amount_0, amount_1) = launch.pool_amounts();
+    assert_eq!(token_amount(&mut banks_client, token_0_vault).await, amount_0);
+    assert_eq!(token_amount(&mut banks_client, token_1_vault).await, amount_1);
+    // A new pool takes all of both sides, its LP goes to lp_recipient
+    assert_eq!(token_amount(&mut banks_client, lp_recipient_token_lp).await, isqrt(amount_0 as u128 * amount_1 as u128) as u64);
+    assert_eq!(token_amount(&mut banks_client, launch.leftover_token(launch.token_1_mint)).await, 0);
+}
+
+#[tokio::test]
+async fn joins_existing_raydium_cp_pool() {
+    let mut launch = Launch::new(MigrationTarget::RaydiumCp);
+    // Someone seeded the pool first at the launching price
+    let (amount_0, amount_1) = launch.pool_amounts();
+    let pool_amounts = (amount_0 / 10, amount_1 / 10);
+    let lp_supply = isqrt(pool_amounts.0 as u128 * pool_amounts.1 as u128) as u64;
+    let accounts = raydium_cp_accounts(&mut launch, pool_amounts, lp_supply);
+    let (pool_state, lp_recipient_token_lp) = (accounts.pool_state, accounts.lp_recipient_token_lp.unwrap());
+    let (token_0_vault, token_1_vault) = (accounts.token_0_vault, accounts.token_1_vault);
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateLiquidity {}.data(),
+    };
+
+    let (mut banks_client, result) = launch.process(instruction, &[]).await;
+    result.unwrap();
+
+    assert_migrated(&mut banks_client, &launch, pool_state).await;
+    assert_leftovers_sent(&mut banks_client, &launch, token_0_vault, token_1_vault, pool_amounts).await;
+    assert_eq!(token_amount(&mut banks_client, lp_recipient_token_lp).await, lp_supply * 10);
+    // The pool keeps the open time it was created with
+    assert_eq!(bonding_curve(&mut banks_client, &launch).await.pool_open_time, 42);
+}
+
+#[tokio::test]
+async fn migrates_into_raydium_clmm_pool() {
+    let mut launch = Launch::new(MigrationTarget::RaydiumClmm);
+    let clmm = raydium_amm_v3::ID;
+    let amm_config = Pubkey::new_unique();
+    let (token_0_mint, token_1_mint) = (launch.token_0_mint, launch.token_1_mint);
+    let pool_state = Pubkey::find_program_address(
+        &[raydium_amm_v3::states::POOL_SEED.as_bytes(), amm_config.as_ref(), token_0_mint.as_ref(), token_1_mint.as_ref()],
+        &clmm,
+    )
+    .0;
+    let vault = |mint: &Pubkey| Pubkey::find_program_address(&[raydium_amm_v3::states::POOL_VAULT_SEED.as_bytes(), pool_state.as_ref(), mint.as_ref()], &clmm).0;
+    let (token_0_vault, token_1_vault) = (vault(&token_0_mint), vault(&token_1_mint));
+    let position_nft_mint = Keypair::new();
+
+    let config = raydium_amm_v3::states::AmmConfig { tick_spacing: 60, ..Default::default() };
+    add_program_account(&mut launch.program_test, amm_config, clmm, &config, 0);
+    launch.add_vault(token_0_vault, token_0_mint, &clmm, 0);
+    launch.add_vault(token_1_vault, token_1_mint, &clmm, 0);
+
+    let accounts = crate::accounts::MigrateLiquidityClmm {
+        global: launch.global(),
+        mint: launch.mint,
+        quote_mint: native_mint::ID,
+        bonding_curve: launch.bonding_curve(),
+        bonding_curve_vault: launch.bonding_curve_vault(),
+        associated_bonding_curve: launch.associated_bonding_curve(),
+        quote_vault: None,
+        lp_recipient: launch.lp_recipient,
+        clmm_program: clmm,
+        creator: launch.creator.pubkey(),
+        amm_config,
+        pool_state,
+        token_0_mint,
+        token_1_mint,
+        creator_token_0: launch.creator_token(token_0_mint),
+        creator_token_1: launch.creator_token(token_1_mint),
+        token_0_vault,
+        token_1_vault,
+        leftover_token_0: Some(launch.leftover_token(token_0_mint)),
+        leftover_token_1: Some(launch.leftover_token(token_1_mint)),
+        observation_state: Pubkey::find_program_address(&[raydium_amm_v3::states::OBSERVATION_SEED.as_bytes(), pool_state.as_ref()], &clmm).0,
+        tick_array_bitmap: Pubkey::find_program_address(&[raydium_amm_v3::states::POOL_TICK_ARRAY_BITMAP_SEED.as_bytes(), pool_state.as_ref()], &clmm).0,
+        position_nft_mint: position_nft_mint.pubkey(),
+        position_nft_account: Pubkey::new_unique(),
+        protocol_position: Pubkey::new_unique(),
+        tick_array_lower: Pubkey::new_unique(),
+        tick_array_upper: Pubkey::new_unique(),
+        personal_position: Pubkey::new_unique(),
+        token_program: spl_token::ID,
+        token_program_2022: anchor_spl::token_2022::ID,
+        mint_token_program: spl_token::ID,
+        token_0_program: spl_token::ID,
+        token_1_program: spl_token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateLiquidityClmm { tick_lower_index: -443_580, tick_upper_index: 443_580 }.data(),
+    };
+
+    let (mut banks_client, result) = launch.process(instruction, &[&position_nft_mint]).await;
+    result.unwrap();
+
+    assert_migrated(&mut banks_client, &launch, pool_state).await;
+    assert_leftovers_sent(&mut banks_client, &launch, token_0_vault, token_1_vault, (0, 0)).await;
+}
+
+#[tokio::test]
+async fn migrates_into_orca_whirlpool() {
+    let mut launch = Launch::new(MigrationTarget::OrcaWhirlpool);
+    let whirlpool_program = whirlpool_cpi::ID;
+    let tick_spacing: u16 = 64;
+    let whirlpools_config = Pubkey::new_unique();
+    let (token_0_mint, token_1_mint) = (launch.token_0_mint, launch.token_1_mint);
+    let whirlpool = Pubkey::find_program_address(
+        &[b"whirlpool", whirlpools_config.as_ref(), token_0_mint.as_ref(), token_1_mint.as_ref(), &tick_spacing.to_le_bytes()],
+        &whirlpool_program,
+    )
+    .0;
+    let (token_0_vault, token_1_vault, position_mint) = (Keypair::new(), Keypair::new(), Keypair::new());
+    launch.add_vault(token_0_vault.pubkey(), token_0_mint, &whirlpool_program, 0);
+    launch.add_vault(token_1_vault.pubkey(), token_1_mint, &whirlpool_program, 0);
+    let lp_recipient_position_token = get_associated_token_address(&launch.lp_recipient, &position_mint.pubkey());
+
+    let accounts = crate::accounts::MigrateLiquidityWhirlpool {
+        global: launch.global(),
+        mint: launch.mint,
+        quote_mint: native_mint::ID,
+        bonding_curve: launch.bonding_curve(),
+        bonding_curve_vault: launch.bonding_curve_vault(),
+        associated_bonding_curve: launch.associated_bonding_curve(),
+        quote_vault: None,
+        lp_recipient: launch.lp_recipient,
+        lp_recipient_position_token,
+        whirlpool_program,
+        creator: launch.creator.pubkey(),
+        whirlpools_config,
+        fee_tier: Pubkey::new_unique(),
+        whirlpool,
+        token_0_mint,
+        token_1_mint,
+        creator_token_0: launch.creator_token(token_0_mint),
+        creator_token_1: launch.creator_token(token_1_mint),
+        leftover_token_0: Some(launch.leftover_token(token_0_mint)),
+        leftover_token_1: Some(launch.leftover_token(token_1_mint)),
+        token_0_vault: token_0_vault.pubkey(),
+        token_1_vault: token_1_vault.pubkey(),
+        tick_array_lower: Pubkey::new_unique(),
+        tick_array_upper: Pubkey::new_unique(),
+        position: Pubkey::find_program_address(&[b"position", position_mint.pubkey().as_ref()], &whirlpool_program).0,
+        position_mint: position_mint.pubkey(),
+        position_token_account: launch.creator_token(position_mint.pubkey()),
+        token_program: spl_token::ID,
+        mint_token_program: spl_token::ID,
+        associated_token_program: associated_token::ID,
+        system_program: system_program::ID,
+        rent: sysvar::rent::ID,
+    };
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateLiquidityWhirlpool { tick_spacing, tick_lower_index: -443_584, tick_upper_index: 443_584 }.data(),
+    };
+
+    let (mut banks_client, result) = launch.process(instruction, &[&token_0_vault, &token_1_vault, &position_mint]).await;
+    result.unwrap();
+
+    assert_migrated(&mut banks_client, &launch, whirlpool).await;
+    assert_leftovers_sent(&mut banks_client, &launch, token_0_vault.pubkey(), token_1_vault.pubkey(), (0, 0)).await;
+    // The position NFT moved from the caller to lp_recipient
+    assert_eq!(token_amount(&mut banks_client, lp_recipient_position_token).await, 1);
+    assert_eq!(token_amount(&mut banks_client, launch.creator_token(position_mint.pubkey())).await, 0);
+}
+
+#[tokio::test]
+async fn migrates_into_meteora_pool() {
+    let mut launch = Launch::new(MigrationTarget::Meteora);
+    let (accounts, position_nft_mint) = meteora_accounts(&mut launch);
+    let (pool, token_0_vault, token_1_vault) = (accounts.pool, accounts.token_0_vault, accounts.token_1_vault);
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateLiquidityMeteora {}.data(),
+    };
+
+    let (mut banks_client, result) = launch.process(instruction, &[&position_nft_mint]).await;
+    result.unwrap();
+
+    assert_migrated(&mut banks_client, &launch, pool).await;
+    assert_leftovers_sent(&mut banks_client, &launch, token_0_vault, token_1_vault, (0, 0)).await;
+}
+
+#[tokio::test]
+async fn rejects_other_migration_target() {
+    let mut launch = Launch::new(MigrationTarget::RaydiumCp);
+    let (accounts, position_nft_mint) = meteora_accounts(&mut launch);
+    let instruction = Instruction {
+        program_id: crate::ID,
+        accounts: accounts.to_account_metas(None),
+        data: crate::instruction::MigrateLiquidityMeteora {}.data(),
+    };
+
+    let (mut banks_client, result) = launch.process(instruction, &[&position_nft_mint]).await;
+
+    assert_eq!(
+        result.unwrap_err().unwrap(),
+        TransactionError::InstructionError(0, InstructionError::Custom(Errors::InvalidMigrationTarget.into())),
+    );
+    let bonding_curve = bonding_curve(&mut banks_client, &launch).await;
+    assert!(!bonding_curve.migrated);
+    assert_eq!(bonding_curve.sol_reserves, SOL_RESERVES);
+}
@@ -0,0 +1,295 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{self, Burn, Mint, TokenAccount, TransferChecked};
+use raydium_cp_swap::cpi;
+
+use crate::{
+    constants::{BASE_POINTS, TEMP_WSOL_SEED},
+    errors::Errors,
+    state::{BondingCurve, Global, PoolRegistry},
+    utils::{close_temp_wsol, open_temp_wsol, proxy_fee, token_balance},
+};
+
+/// Fees a proxy swap takes from its input
+pub struct ProxyFees {
+    /// Platform fee at the normal proxy rate
+    pub fee_amount: u64,
+    /// Anti-sniping surcharge on top, split below
+    pub surcharge_amount: u64,
+    pub creator_amount: u64,
+    pub buyback_amount: u64,
+    pub platform_amount: u64,
+}
+
+impl ProxyFees {
+    /// Fees on `amount` of input, the surcharge only applying to pools of a launch
+    pub fn new(global: &Global, bonding_curve: Option<&Account<BondingCurve>>, amount: u64) -> Result<Self> {
+        let now: u64 = Clock::get()?.unix_timestamp.try_into().unwrap();
+        let surcharge_bps = bonding_curve.map_or(0, |bonding_curve| surcharge_bps(global, bonding_curve, now));
+
+        let surcharge_amount = proxy_fee(amount, surcharge_bps);
+        let creator_amount = (surcharge_amount as u128 * global.proxy_surcharge_creator_bps as u128 / BASE_POINTS as u128) as u64;
+        let buyback_amount = (surcharge_amount as u128 * global.proxy_surcharge_buyback_bps as u128 / BASE_POINTS as u128) as u64;
+
+        Ok(ProxyFees {
+            fee_amount: proxy_fee(amount, global.proxy_fee_bps),
+            surcharge_amount,
+            creator_amount,
+            buyback_amount,
+            platform_amount: surcharge_amount - creator_amount - buyback_amount,
+        })
+    }
+
+    pub fn total(&self) -> u64 {
+        self.fee_amount + self.surcharge_amount
+    }
+}
+
+/// Check the proxy serves the pool, a registered pool requiring the launch it belongs to
+pub fn require_proxy_pool(global: &Global, pool_registry: &AccountInfo, bonding_curve: Option<&Account<BondingCurve>>) -> Result<()> {
+    if pool_registry.owner == &crate::ID {
+        let registry = PoolRegistry::try_deserialize(&mut pool_registry.try_borrow_data()?.as_ref())?;
+        let bonding_curve = bonding_curve.ok_or(Errors::InvalidProxyPool)?;
+        require!(bonding_curve.key() == registry.bonding_curve, Errors::InvalidProxyPool);
+        return Ok(());
+    }
+
+    // Launches migrated before the registry existed still pass their curve to pay the surcharge
+    require!(bonding_curve.is_some() || global.proxy_allow_any_pool, Errors::InvalidProxyPool);
+    Ok(())
+}
+
+/// Surcharge in bps on top of the proxy fee, decaying linearly to nothing over the window after the pool opens
+pub fn surcharge_bps(global: &Global, bonding_curve: &BondingCurve, now: u64) -> u16 {
+    let window = global.proxy_surcharge_window;
+    // Launches migrated before the open time was recorded carry no surcharge
+    if window == 0 || bonding_curve.pool_open_time == 0 {
+        return 0;
+    }
+
+    let elapsed = now.saturating_sub(bonding_curve.pool_open_time);
+    if elapsed >= window {
+        return 0;
+    }
+    (global.proxy_surcharge_start_bps as u128 * (window - elapsed) as u128 / window as u128) as u16
+}
+
+/// Accounts of a Raydium CP proxy swap, a side without token account swapping native SOL
+pub struct ProxySwapAccounts<'info> {
+    pub payer: AccountInfo<'info>,
+    pub cp_swap_program: AccountInfo<'info>,
+    pub authority: AccountInfo<'info>,
+    pub amm_config: AccountInfo<'info>,
+    pub pool_state: AccountInfo<'info>,
+    pub input_token_account: Option<AccountInfo<'info>>,
+    pub output_token_account: Option<AccountInfo<'info>>,
+    pub input_vault: AccountInfo<'info>,
+    pub output_vault: AccountInfo<'info>,
+    pub input_token_program: AccountInfo<'info>,
+    pub output_token_program: AccountInfo<'info>,
+    pub input_token_mint: AccountInfo<'info>,
+    pub output_token_mint: AccountInfo<'info>,
+    pub observation_state: AccountInfo<'info>,
+    pub temp_wsol_account: Option<AccountInfo<'info>>,
+    pub temp_wsol_bump: u8,
+    pub system_program: Option<AccountInfo<'info>>,
+}
+
+impl<'info> ProxySwapAccounts<'info> {
+    /// Mint and token program of the native SOL side, if any
+    fn native_side(&self) -> Option<(&AccountInfo<'info>, &AccountInfo<'info>)> {
+        if self.input_token_account.is_none() {
+            Some((&self.input_token_mint, &self.input_token_program))
+        } else if self.output_token_account.is_none() {
+            Some((&self.output_token_mint, &self.output_token_program))
+        } else {
+            None
+        }
+    }
+
+    /// Open the temporary WSOL account of a native SOL side, funded with the whole `amount_in` when it pays
+    pub fn open_native_side(&self, amount_in: u64) -> Result<()> {
+        let Some((native_side_mint, native_side_program)) = self.native_side() else {
+            return Ok(());
+        };
+        require!(native_side_mint.key() == native_mint::ID, Errors::NativeSideNotSol);
+
+        let wrapped = if self.input_token_account.is_none() { amount_in } else { 0 };
+        let payer_key = self.payer.key();
+        let temp_wsol_seeds = &[TEMP_WSOL_SEED.as_bytes(), payer_key.as_ref(), &[self.temp_wsol_bump]];
+        open_temp_wsol(
+            &self.payer,
+            self.temp_wsol_account.as_ref().ok_or(Errors::MissingAccount)?,
+            native_side_mint,
+            native_side_program,
+            self.system_program.as_ref().ok_or(Errors::MissingAccount)?,
+            wrapped,
+            &[&temp_wsol_seeds[..]],
+        )
+    }
+
+    /// Unwrap what is left of the temporary WSOL account back to the payer
+    pub fn close_native_side(&self) -> Result<()> {
+        let Some((_, native_side_program)) = self.native_side() else {
+            return Ok(());
+        };
+        close_temp_wsol(&self.payer, self.temp_wsol_account.as_ref().ok_or(Errors::MissingAccount)?, native_side_program)
+    }
+
+    /// Payer account swapped from, the temporary WSOL account when paying native SOL
+    pub fn input_account(&self) -> Result<AccountInfo<'info>> {
+        self.side_account(&self.input_token_account)
+    }
+
+    /// Payer account swapped into, the temporary WSOL account when receiving native SOL
+    pub fn output_account(&self) -> Result<AccountInfo<'info>> {
+        self.side_account(&self.output_token_account)
+    }
+
+    fn side_account(&self, token_account: &Option<AccountInfo<'info>>) -> Result<AccountInfo<'info>> {
+        match token_account {
+            Some(token_account) => Ok(token_account.clone()),
+            None => Ok(self.temp_wsol_account.as_ref().ok_or(Errors::MissingAccount)?.clone()),
+        }
+    }
+
+    pub fn swap_context<'a, 'b, 'c>(&self) -> Result<CpiContext<'a, 'b, 'c, 'info, cpi::accounts::Swap<'info>>> {
+        Ok(CpiContext::new(
+            self.cp_swap_program.clone(),
+            cpi::accounts::Swap {
+                payer: self.payer.clone(),
+                authority: self.authority.clone(),
+                amm_config: self.amm_config.clone(),
+                pool_state: self.pool_state.clone(),
+                input_token_account: self.input_account()?,
+                output_token_account: self.output_account()?,
+                input_vault: self.input_vault.clone(),
+                output_vault: self.output_vault.clone(),
+                input_token_program: self.input_token_program.clone(),
+                output_token_program: self.output_token_program.clone(),
+                input_token_mint: self.input_token_mint.clone(),
+                output_token_mint: self.output_token_mint.clone(),
+                observation_state: self.observation_state.clone(),
+            },
+        ))
+    }
+
+    /// Burn the buyback share, bought with the swap's own route when the input is the quote
+    pub fn buy_back(&self, fees: &ProxyFees, launch_mint: Option<Pubkey>) -> Result<()> {
+        if fees.buyback_amount == 0 {
+            return Ok(());
+        }
+
+        let launch_mint = launch_mint.ok_or(Errors::MissingAccount)?;
+        if self.input_token_mint.key() == launch_mint {
+            return burn_buyback(&self.payer, &self.input_account()?, &self.input_token_mint, &self.input_token_program, fees.buyback_amount);
+        }
+
+        let output_token_account = self.output_account()?;
+        let bought_before = token_balance(&output_token_account)?;
+        cpi::swap_base_input(self.swap_context()?, fees.buyback_amount, 0)?;
+        let bought = token_balance(&output_token_account)? - bought_before;
+        burn_buyback(&self.payer, &output_token_account, &self.output_token_mint, &self.output_token_program, bought)
+    }
+}
+
+/// Pay the platform and creator parts of `fees` out of the payer input account
+pub fn pay_fees<'info>(
+    fees: &ProxyFees,
+    payer: &AccountInfo<'info>,
+    input_token_account: &AccountInfo<'info>,
+    input_token_mint: &InterfaceAccount<'info, Mint>,
+    input_token_program: &AccountInfo<'info>,
+    fee_recipient_token_account: &AccountInfo<'info>,
+    creator_token_account: Option<&InterfaceAccount<'info, TokenAccount>>,
+    bonding_curve: Option<&Account<'info, BondingCurve>>,
+) -> Result<()> {
+    let transfer = |to: AccountInfo<'info>, amount: u64| {
+        token_interface::transfer_checked(
+            CpiContext::new(
+                input_token_program.clone(),
+                TransferChecked {
+                    from: input_token_account.clone(),
+                    mint: input_token_mint.to_account_info(),
+                    to,
+                    authority: payer.clone(),
+                },
+            ),
+            amount,
+            input_token_mint.decimals,
+        )
+    };
+
+    let platform_amount = fees.fee_amount + fees.platform_amount;
+    if platform_amount > 0 {
+        transfer(fee_recipient_token_account.clone(), platform_amount)?;
+    }
+
+    if fees.creator_amount > 0 {
+        let creator_token_account = creator_token_account.ok_or(Errors::MissingAccount)?;
+        let creator = bonding_curve.ok_or(Errors::MissingAccount)?.creator;
+        require!(creator_token_account.owner == creator, Errors::NotAuthorized);
+        require!(creator_token_account.mint == input_token_mint.key(), Errors::InvalidValue);
+        transfer(creator_token_account.to_account_info(), fees.creator_amount)?;
+    }
+
+    Ok(())
+}
+
+/// Burn launched tokens the payer holds, bought back or taken from a sell
+pub fn burn_buyback<'info>(
+    payer: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    token_interface::burn(
+        CpiContext::new(
+            token_program.clone(),
+            Burn {
+                mint: mint.clone(),
+                from: token_account.clone(),
+                authority: payer.clone(),
+            },
+        ),
+        amount,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn global(start_bps: u16, window: u64) -> Global {
+        Global {
+            proxy_surcharge_start_bps: start_bps,
+            proxy_surcharge_window: window,
+            ..Default::default()
+        }
+    }
+
+    fn bonding_curve(pool_open_time: u64) -> BondingCurve {
+        BondingCurve {
+            pool_open_time,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn surcharge_decays_over_window() {
+        let global = global(5_000, 100);
+        let bonding_curve = bonding_curve(1_000);
+        assert_eq!(surcharge_bps(&global, &bonding_curve, 900), 5_000);
+        assert_eq!(surcharge_bps(&global, &bonding_curve, 1_000), 5_000);
+        assert_eq!(surcharge_bps(&global, &bonding_curve, 1_050), 2_500);
+        assert_eq!(surcharge_bps(&global, &bonding_curve, 1_099), 50);
+        assert_eq!(surcharge_bps(&global, &bonding_curve, 1_100), 0);
+    }
+
+    #[test]
+    fn surcharge_off_without_window_or_open_time() {
+        assert_eq!(surcharge_bps(&global(5_000, 0), &bonding_curve(1_000), 1_000), 0);
+        assert_eq!(surcharge_bps(&global(5_000, 100), &bonding_curve(0), 1_000), 0);
+    }
+}
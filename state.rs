@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 
+use crate::constants::DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS;
+use anchor_spl::token::spl_token::native_mint;
+
 #[account]
 #[derive(Default)]
 pub struct Global {
@@ -28,6 +31,74 @@ pub struct Global {
     pub token_platform_reserve: u64,
 
     pub token_pool_reserve: u64,
+
+    /// Number of launches created, used to assign `BondingCurve::launch_id`.
+    pub launch_count: u64,
+
+    /// Shortest allowed time between the sale opening and its deadline.
+    pub min_sale_duration: u64,
+
+    /// Longest allowed time between the sale opening and its deadline, 0 for no limit.
+    pub max_sale_duration: u64,
+
+    /// Highest transfer fee a Token-2022 launch may charge.
+    pub max_transfer_fee_bps: u16,
+
+    /// Highest per-transfer fee cap a Token-2022 launch may set.
+    pub max_transfer_fee: u64,
+
+    /// Share of harvested transfer fees paid to the creator, the rest goes to the platform.
+    pub transfer_fee_creator_share_bps: u16,
+
+    /// Allowed range of launch decimals, only `DEFAULT_TOKEN_DECIMALS` while unset.
+    pub min_token_decimals: u8,
+
+    pub max_token_decimals: u8,
+
+    /// Receives what an existing pool does not take at migration, `fee_recipient` while unset.
+    pub migration_leftover_recipient: Pubkey,
+
+    /// Allowed deviation of the pool price from the launching price, `DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS` while unset.
+    pub migration_price_tolerance_bps: u16,
+
+    /// Platform fee taken from the input of proxy swaps.
+    pub proxy_fee_bps: u16,
+
+    /// Let proxy swaps route through pools no launch migrated to.
+    pub proxy_allow_any_pool: bool,
+
+    /// Surcharge on top of the proxy fee when a launch pool opens, decaying to 0 over `proxy_surcharge_window`.
+    pub proxy_surcharge_start_bps: u16,
+
+    /// Seconds after the pool opens during which the surcharge applies, 0 for no surcharge.
+    pub proxy_surcharge_window: u64,
+
+    /// Shares of the surcharge paid to the creator and burned as buyback, the rest goes to the platform.
+    pub proxy_surcharge_creator_bps: u16,
+
+    pub proxy_surcharge_buyback_bps: u16,
+
+    /// Shortest LP lock a launch may choose, a 0 lock stays permanent.
+    pub min_lp_lock_duration: u64,
+}
+
+impl Global {
+    /// Owner of the accounts paid the migration leftovers.
+    pub fn leftover_recipient(&self) -> Pubkey {
+        if self.migration_leftover_recipient == Pubkey::default() {
+            self.fee_recipient
+        } else {
+            self.migration_leftover_recipient
+        }
+    }
+
+    pub fn price_tolerance_bps(&self) -> u16 {
+        if self.migration_price_tolerance_bps == 0 {
+            DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS
+        } else {
+            self.migration_price_tolerance_bps
+        }
+    }
 }
 
 #[account]
@@ -70,6 +141,126 @@ pub struct BondingCurve {
     pub migrated: bool,
 
     pub withdrawed: bool,
+
+    /// Purchases are minted as receipt tokens instead of recorded in `UserPurchase`.
+    pub receipt_enabled: bool,
+
+    pub mint: Pubkey,
+
+    /// Payer of `create_token`.
+    pub creator: Pubkey,
+
+    pub created_at: i64,
+
+    pub launch_id: u64,
+
+    pub bump: u8,
+
+    pub vault_bump: u8,
+
+    /// Buying is halted by the creator or authority, selling stays open for refunds.
+    pub paused: bool,
+
+    /// Token-2022 transfer fee of the mint, 0 if the mint has none.
+    pub transfer_fee_bps: u16,
+
+    /// Token the sale is priced and paid in, the native mint for SOL launches.
+    pub quote_mint: Pubkey,
+
+    /// Venue the liquidity migrates to once the sale completes.
+    pub migration_target: MigrationTarget,
+
+    /// What happens to the LP tokens minted at migration.
+    pub lp_disposition: LpDisposition,
+
+    /// Seconds the LP stays locked after migration, 0 locks it forever.
+    pub lp_lock_duration: u64,
+
+    /// Shares of collected pool fees, the platform receives the rest.
+    pub pool_fee_creator_bps: u16,
+
+    pub pool_fee_buyback_bps: u16,
+
+    /// Seconds between migration and the pool accepting trades.
+    pub pool_open_delay: u64,
+
+    /// Time the migrated pool opens for trading.
+    pub pool_open_time: u64,
+
+    /// Pool the launch migrated to, default until migration.
+    pub pool: Pubkey,
+
+    /// LP mint of the pool, default for venues whose liquidity is a position NFT.
+    pub pool_lp_mint: Pubkey,
+
+    /// Config the pool was created under (amm config, whirlpools config or meteora config).
+    pub pool_amm_config: Pubkey,
+
+    /// Set by fallback migrations, which leave the launch without a pool and its reserves escrowed in the curve.
+    pub no_pool: bool,
+}
+
+impl BondingCurve {
+    /// Launches created before quote tokens existed have no quote mint set and raise SOL.
+    pub fn quote_mint(&self) -> Pubkey {
+        if self.quote_mint == Pubkey::default() {
+            native_mint::ID
+        } else {
+            self.quote_mint
+        }
+    }
+
+    /// Whether the sale is paid in native SOL held as lamports by the vault.
+    pub fn is_sol_quote(&self) -> bool {
+        self.quote_mint() == native_mint::ID
+    }
+
+    /// Time at which the first purchases (whitelist or public) are accepted.
+    pub fn sale_start_at(&self) -> u64 {
+        if self.whitelisted {
+            self.whitelist_start_at
+        } else {
+            self.investing_start_at
+        }
+    }
+
+    /// Whether any tokens have been bought from the curve.
+    pub fn has_purchases(&self) -> bool {
+        self.sol_reserves > 0 || self.token_reserves < self.token_total_supply
+    }
+
+    pub fn is_creator_or_authority(&self, key: &Pubkey, global: &Global) -> bool {
+        *key == self.creator || *key == global.authority
+    }
+}
+
+/// Direction of a `trade`, buying or selling the launched token
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TradeSide {
+    Buy,
+    Sell,
+}
+
+/// Venue a launch migrates its liquidity to
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MigrationTarget {
+    #[default]
+    RaydiumCp,
+    RaydiumClmm,
+    OrcaWhirlpool,
+    Meteora,
+}
+
+/// What happens to the LP tokens minted at migration
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LpDisposition {
+    /// Held by the launch locker until `lp_lock_duration` passes.
+    #[default]
+    Lock,
+    /// Sent to `Global::lp_recipient`.
+    Recipient,
+    /// Burned, leaving the liquidity in the pool forever.
+    Burn,
 }
 
 #[account]
@@ -81,3 +272,47 @@ pub struct UserPurchase {
 
     pub token_amount: u64,
 }
+
+/// Program-owned custody of the LP tokens of one launch
+#[account]
+#[derive(Default)]
+pub struct LpLocker {
+    pub bonding_curve: Pubkey,
+
+    pub mint: Pubkey,
+
+    pub lp_mint: Pubkey,
+
+    /// Receives the LP once unlocked.
+    pub beneficiary: Pubkey,
+
+    /// LP tokens still held by the locker.
+    pub amount: u64,
+
+    pub locked_at: i64,
+
+    /// Time the LP can be released, 0 if locked forever.
+    pub unlock_at: u64,
+
+    /// Pool sqrt(k) and LP supply when fees were last collected, pricing the LP.
+    pub fee_sqrt_k: u128,
+
+    pub fee_lp_supply: u64,
+
+    pub bump: u8,
+}
+
+/// Raydium CP pool a launch migrated to, keyed by the pool so proxy swaps into it cannot leave the launch out and skip its surcharge
+#[account]
+#[derive(Default)]
+pub struct PoolRegistry {
+    pub bonding_curve: Pubkey,
+
+    pub mint: Pubkey,
+}
+
+impl LpLocker {
+    pub fn is_permanent(&self) -> bool {
+        self.unlock_at == 0
+    }
+}
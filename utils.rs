@@ -1,5 +1,371 @@
-use anchor_lang::prelude::*;
-use solana_program::keccak;
+use anchor_lang::{prelude::*, system_program};
+use anchor_spl::token::{self, Mint, Token, TokenAccount, TransferChecked};
+use anchor_spl::token::spl_token::state::Account as TokenState;
+use anchor_spl::token_interface::TokenAccount as TokenAccountInterface;
+use anchor_spl::token_2022::spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions},
+    state::Mint as MintState,
+};
+use solana_program::{keccak, program_pack::Pack};
+
+use crate::{constants::{BASE_POINTS, DEFAULT_TOKEN_DECIMALS}, errors::Errors, state::{BondingCurve, Global, LpDisposition, MigrationTarget}};
+
+/// Accounts moving an SPL quote token in and out of the curve vault
+pub struct QuoteAccounts<'info> {
+    pub mint: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub decimals: u8,
+}
+
+/// Collect the quote token accounts of a launch, None for SOL launches
+pub fn quote_accounts<'info>(
+    bonding_curve: &BondingCurve,
+    quote_mint: &Option<Box<Account<'info, Mint>>>,
+    quote_vault: &Option<Box<Account<'info, TokenAccount>>>,
+    quote_token_program: &Option<Program<'info, Token>>,
+) -> Result<Option<QuoteAccounts<'info>>> {
+    if bonding_curve.is_sol_quote() {
+        return Ok(None);
+    }
+
+    let mint = quote_mint.as_ref().ok_or(Errors::MissingAccount)?;
+    require_keys_eq!(mint.key(), bonding_curve.quote_mint(), Errors::InvalidQuoteMint);
+    let vault = quote_vault.as_ref().ok_or(Errors::MissingAccount)?;
+    let token_program = quote_token_program.as_ref().ok_or(Errors::MissingAccount)?;
+
+    Ok(Some(QuoteAccounts {
+        mint: mint.to_account_info(),
+        vault: vault.to_account_info(),
+        token_program: token_program.to_account_info(),
+        decimals: mint.decimals,
+    }))
+}
+
+/// Pay `amount` of quote out of the curve vault.
+/// SOL is sent as lamports to `to`, an SPL quote moves from the quote vault to the token account `to`.
+pub fn pay_quote_from_vault<'info>(
+    system_program: &AccountInfo<'info>,
+    bonding_curve_vault: &AccountInfo<'info>,
+    quote: Option<&QuoteAccounts<'info>>,
+    to: AccountInfo<'info>,
+    amount: u64,
+    vault_signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+
+    match quote {
+        None => system_program::transfer(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: bonding_curve_vault.clone(),
+                    to,
+                },
+                vault_signer_seeds,
+            ),
+            amount,
+        ),
+        Some(quote) => token::transfer_checked(
+            CpiContext::new_with_signer(
+                quote.token_program.clone(),
+                TransferChecked {
+                    from: quote.vault.clone(),
+                    mint: quote.mint.clone(),
+                    to,
+                    authority: bonding_curve_vault.clone(),
+                },
+                vault_signer_seeds,
+            ),
+            amount,
+            quote.decimals,
+        ),
+    }
+}
+
+/// Token-2022 transfer fee charged when moving `amount` of `mint`, 0 for mints without one
+pub fn transfer_fee_for(mint: &AccountInfo, amount: u64) -> Result<u64> {
+    let data = mint.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<MintState>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => {
+            let epoch = Clock::get()?.epoch;
+            Ok(config.calculate_epoch_fee(epoch, amount).ok_or(Errors::MathOverflow)?)
+        }
+        Err(_) => Ok(0),
+    }
+}
+
+/// Validate the decimals of a new token against the global range
+pub fn validate_decimals(global: &Global, decimals: u8) -> Result<()> {
+    if global.max_token_decimals == 0 {
+        require!(decimals == DEFAULT_TOKEN_DECIMALS, Errors::InvalidDecimals);
+    } else {
+        require!(
+            decimals >= global.min_token_decimals && decimals <= global.max_token_decimals,
+            Errors::InvalidDecimals
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate what a new launch does with its LP against its migration target and the global minimum lock
+pub fn validate_lp_disposition(global: &Global, migration_target: MigrationTarget, lp_disposition: LpDisposition, lp_lock_duration: u64) -> Result<()> {
+    // Only Raydium CP mints fungible LP tokens the locker can hold or burn, position NFTs go to lp_recipient
+    if migration_target != MigrationTarget::RaydiumCp {
+        require!(lp_disposition == LpDisposition::Recipient, Errors::InvalidMigrationTarget);
+    }
+    // A 0 duration locks forever
+    if lp_disposition == LpDisposition::Lock && lp_lock_duration > 0 {
+        require!(lp_lock_duration >= global.min_lp_lock_duration, Errors::LpLockTooShort);
+    }
+
+    Ok(())
+}
+
+/// Convert a global token amount from `DEFAULT_TOKEN_DECIMALS` to `decimals`
+pub fn scale_token_amount(amount: u64, decimals: u8) -> Result<u64> {
+    if decimals >= DEFAULT_TOKEN_DECIMALS {
+        let factor = 10u64.pow((decimals - DEFAULT_TOKEN_DECIMALS).into());
+        Ok(amount.checked_mul(factor).ok_or(Errors::MathOverflow)?)
+    } else {
+        let factor = 10u64.pow((DEFAULT_TOKEN_DECIMALS - decimals).into());
+        // Dropping precision would break the supply split
+        require!(amount % factor == 0, Errors::InvalidDecimals);
+        Ok(amount / factor)
+    }
+}
+
+/// SOL value of `token_amount` at `price` lamports per whole token
+pub fn token_value(token_amount: u64, price: u64, decimals: u8) -> Result<u64> {
+    let value = (token_amount as u128)
+        .checked_mul(price as u128)
+        .and_then(|x| x.checked_div(10u128.pow(decimals.into())))
+        .and_then(|x| u64::try_from(x).ok())
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(value)
+}
+
+/// Deviation in bps of `quote_amount` for `token_amount` from its value at `price`
+pub fn price_deviation_bps(quote_amount: u64, token_amount: u64, price: u64, decimals: u8) -> Result<u64> {
+    let expected = token_value(token_amount, price, decimals)?;
+    require!(expected > 0, Errors::InvalidPrice);
+
+    let deviation = (quote_amount as u128).abs_diff(expected as u128) * BASE_POINTS as u128 / expected as u128;
+    Ok(deviation.try_into().unwrap_or(u64::MAX))
+}
+
+/// Grow a program account created under an older layout to `space`, the payer topping up its rent
+pub fn resize_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+) -> Result<()> {
+    if account.data_len() >= space {
+        return Ok(());
+    }
+
+    let required = Rent::get()?.minimum_balance(space);
+    let current = account.lamports();
+    if required > current {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            required - current,
+        )?;
+    }
+
+    // New fields read as zero, which every field added since treats as unset
+    account.realloc(space, true)?;
+
+    Ok(())
+}
+
+/// Create the payer's temporary WSOL account at its PDA, wrapping `lamports` on top of its rent
+pub fn open_temp_wsol<'info>(
+    payer: &AccountInfo<'info>,
+    temp_wsol: &AccountInfo<'info>,
+    native_mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lamports: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let space = TokenState::LEN;
+    let required = Rent::get()?.minimum_balance(space) + lamports;
+
+    // Lamports sent to the address beforehand would make create_account fail, so top up instead
+    let current = temp_wsol.lamports();
+    if current == 0 {
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                system_program.clone(),
+                system_program::CreateAccount {
+                    from: payer.clone(),
+                    to: temp_wsol.clone(),
+                },
+                signer_seeds,
+            ),
+            required,
+            space as u64,
+            token_program.key,
+        )?;
+    } else {
+        if required > current {
+            system_program::transfer(
+                CpiContext::new(
+                    system_program.clone(),
+                    system_program::Transfer {
+                        from: payer.clone(),
+                        to: temp_wsol.clone(),
+                    },
+                ),
+                required - current,
+            )?;
+        }
+        system_program::allocate(
+            CpiContext::new_with_signer(system_program.clone(), system_program::Allocate { account_to_allocate: temp_wsol.clone() }, signer_seeds),
+            space as u64,
+        )?;
+        system_program::assign(
+            CpiContext::new_with_signer(system_program.clone(), system_program::Assign { account_to_assign: temp_wsol.clone() }, signer_seeds),
+            token_program.key,
+        )?;
+    }
+
+    // A native account holds everything above its rent as wrapped SOL
+    token::initialize_account3(CpiContext::new(
+        token_program.clone(),
+        token::InitializeAccount3 {
+            account: temp_wsol.clone(),
+            mint: native_mint.clone(),
+            authority: payer.clone(),
+        },
+    ))
+}
+
+/// Close the payer's temporary WSOL account, unwrapping all of it back to lamports
+pub fn close_temp_wsol<'info>(payer: &AccountInfo<'info>, temp_wsol: &AccountInfo<'info>, token_program: &AccountInfo<'info>) -> Result<()> {
+    token::close_account(CpiContext::new(
+        token_program.clone(),
+        token::CloseAccount {
+            account: temp_wsol.clone(),
+            destination: payer.clone(),
+            authority: payer.clone(),
+        },
+    ))
+}
+
+/// Payer balance on a proxy swap side, lamports when the side is native SOL
+pub fn proxy_balance<'info>(token_account: &Option<Box<InterfaceAccount<'info, TokenAccountInterface>>>, payer: &AccountInfo<'info>) -> Result<u64> {
+    match token_account {
+        Some(token_account) => token_balance(&token_account.to_account_info()),
+        None => Ok(payer.lamports()),
+    }
+}
+
+/// Balance read from the account data, which CPIs may have changed since deserialization
+pub fn token_balance(token_account: &AccountInfo) -> Result<u64> {
+    let token_account = TokenAccountInterface::try_deserialize(&mut token_account.try_borrow_data()?.as_ref())?;
+    Ok(token_account.amount)
+}
+
+/// Platform fee on `amount` routed through a proxy swap
+pub fn proxy_fee(amount: u64, fee_bps: u16) -> u64 {
+    (amount as u128 * fee_bps as u128 / BASE_POINTS as u128) as u64
+}
+
+/// Q64.64 square root of the price `numerator / denominator` of token 0 in token 1
+pub fn sqrt_price_x64(numerator: u64, denominator: u64) -> Result<u128> {
+    require!(numerator > 0 && denominator > 0, Errors::InvalidPrice);
+    // (n << 64) / d fits in u128, its root carries 32 fractional bits
+    let ratio_x64 = ((numerator as u128) << 64) / denominator as u128;
+    let sqrt_price = isqrt(ratio_x64) << 32;
+    require!(sqrt_price > 0, Errors::InvalidPrice);
+
+    Ok(sqrt_price)
+}
+
+pub fn isqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    let mut x = value;
+    // ceil(x / 2) without the overflow of x + 1 at u128::MAX
+    let mut y = (x >> 1) + (x & 1);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// LP tokens worth the fees accrued since the snapshot, valuing each LP at sqrt(k) / lp_supply
+pub fn pool_fee_lp(lp_amount: u64, snapshot_sqrt_k: u128, snapshot_lp_supply: u64, sqrt_k: u128, lp_supply: u64) -> Result<u64> {
+    if snapshot_sqrt_k == 0 || snapshot_lp_supply == 0 || sqrt_k == 0 {
+        return Ok(0);
+    }
+
+    // LP still worth the snapshot value, the rest is fee growth
+    let keep = (lp_amount as u128)
+        .checked_mul(snapshot_sqrt_k)
+        .map(|x| x / sqrt_k)
+        .and_then(|x| x.checked_mul(lp_supply as u128))
+        .map(|x| x / snapshot_lp_supply as u128)
+        .ok_or(Errors::MathOverflow)?;
+
+    Ok(lp_amount.saturating_sub(u64::try_from(keep).unwrap_or(u64::MAX)))
+}
+
+/// Validate the metadata of a new token
+pub fn validate_token_metadata(
+    token_name: &str,
+    token_symbol: &str,
+    token_uri: &str,
+) -> Result<()> {
+    require!(token_name.len() <= 32, Errors::NameTooLong);
+    require!(token_symbol.len() <= 10, Errors::SymbolTooLong);
+    require!(token_uri.len() <= 200, Errors::UriTooLong);
+
+    Ok(())
+}
+
+/// Validate the sale windows of a launch
+pub fn validate_schedule(
+    global: &Global,
+    token_investing_deadline: u64,
+    investing_start_at: u64,
+    whitelisted: bool,
+    whitelist_start_at: u64,
+    now: u64,
+) -> Result<()> {
+    require!(token_investing_deadline > 0, Errors::InvalidValue);
+    require!(whitelist_start_at <= investing_start_at, Errors::InvalidValue);
+    require!(investing_start_at < token_investing_deadline, Errors::InvalidValue);
+    if whitelisted {
+        require!(whitelist_start_at >= now, Errors::InvalidValue);
+    }
+
+    // The sale lasts from the first purchase window until the deadline
+    let sale_start_at = if whitelisted { whitelist_start_at } else { investing_start_at };
+    let sale_duration = token_investing_deadline - sale_start_at;
+    require!(sale_duration >= global.min_sale_duration, Errors::InvalidSaleDuration);
+    if global.max_sale_duration > 0 {
+        require!(sale_duration <= global.max_sale_duration, Errors::InvalidSaleDuration);
+    }
+
+    Ok(())
+}
 
 /// Verify a Merkle proof
 pub fn verify_merkle_proof(
@@ -41,3 +407,71 @@ pub fn get_leaf_hash(address: &Pubkey, amount: Option<u64>) -> [u8; 32] {
         keccak::hash(address.as_ref()).to_bytes()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_value_at_price() {
+        assert_eq!(token_value(1_500_000, 2_000, 6).unwrap(), 3_000);
+        assert_eq!(token_value(1, 1, 9).unwrap(), 0);
+        assert!(token_value(u64::MAX, u64::MAX, 0).is_err());
+    }
+
+    #[test]
+    fn scale_token_amount_between_decimals() {
+        assert_eq!(scale_token_amount(1_000_000, DEFAULT_TOKEN_DECIMALS).unwrap(), 1_000_000);
+        assert_eq!(scale_token_amount(1_000_000, 9).unwrap(), 1_000_000_000);
+        assert_eq!(scale_token_amount(1_000_000, 3).unwrap(), 1_000);
+        assert!(scale_token_amount(1_000_001, 3).is_err());
+        assert!(scale_token_amount(u64::MAX, 9).is_err());
+    }
+
+    #[test]
+    fn sqrt_price_x64_is_q64() {
+        assert_eq!(sqrt_price_x64(1, 1).unwrap(), 1u128 << 64);
+        assert_eq!(sqrt_price_x64(4, 1).unwrap(), 1u128 << 65);
+        assert_eq!(sqrt_price_x64(1, 4).unwrap(), 1u128 << 63);
+        assert!(sqrt_price_x64(0, 1).is_err());
+        assert!(sqrt_price_x64(1, 0).is_err());
+    }
+
+    #[test]
+    fn isqrt_rounds_down() {
+        assert_eq!(isqrt(0), 0);
+        assert_eq!(isqrt(1), 1);
+        assert_eq!(isqrt(2), 1);
+        assert_eq!(isqrt(3), 1);
+        assert_eq!(isqrt(4), 2);
+        assert_eq!(isqrt(15), 3);
+        assert_eq!(isqrt(16), 4);
+        assert_eq!(isqrt(u64::MAX as u128 * u64::MAX as u128), u64::MAX as u128);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+    }
+
+    #[test]
+    fn pool_fee_lp_prices_fee_growth() {
+        // sqrt(k) grew 10% with the supply unchanged, so 1/11 of the LP is fees
+        assert_eq!(pool_fee_lp(500, 1_000, 1_000, 1_100, 1_000).unwrap(), 46);
+        assert_eq!(pool_fee_lp(500, 1_000, 1_000, 1_000, 1_000).unwrap(), 0);
+        // Deposits grow sqrt(k) and the supply together, which is not fee growth
+        assert_eq!(pool_fee_lp(500, 1_000, 1_000, 2_000, 2_000).unwrap(), 0);
+        assert_eq!(pool_fee_lp(500, 0, 1_000, 1_100, 1_000).unwrap(), 0);
+    }
+
+    #[test]
+    fn price_deviation_in_bps() {
+        assert_eq!(price_deviation_bps(1_000, 1_000_000, 1_000, 6).unwrap(), 0);
+        assert_eq!(price_deviation_bps(1_100, 1_000_000, 1_000, 6).unwrap(), 1_000);
+        assert_eq!(price_deviation_bps(900, 1_000_000, 1_000, 6).unwrap(), 1_000);
+        assert!(price_deviation_bps(1_000, 1_000_000, 0, 6).is_err());
+    }
+
+    #[test]
+    fn proxy_fee_rounds_down() {
+        assert_eq!(proxy_fee(10_000, 100), 100);
+        assert_eq!(proxy_fee(99, 100), 0);
+        assert_eq!(proxy_fee(u64::MAX, BASE_POINTS as u16), u64::MAX);
+    }
+}